@@ -0,0 +1,86 @@
+//! KYC attestations signed off-chain and verified through the ed25519 program
+
+use crate::{error::LaunchpadError, state::MAX_TIERS};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+/// Message signed by the auction's KYC authority,
+/// borsh encoded as buyer (32) | auction (32) | expiry (i64 LE) | tier (u8)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct KycAttestation {
+    pub buyer: Pubkey,
+    pub auction: Pubkey,
+    pub expiry: i64,
+    pub tier: u8,
+}
+
+/// Size of the ed25519 instruction header: signature count and padding
+const HEADER_LEN: usize = 2;
+/// Size of one `Ed25519SignatureOffsets` entry
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+/// Instruction index meaning "this ed25519 instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Verify that the instruction right before the current one is an ed25519 signature
+/// check of a `KycAttestation` for `buyer` and `auction` signed by `authority`,
+/// and return the attested tier
+pub fn verify_attestation(
+    instructions: &AccountInfo,
+    authority: &Pubkey,
+    buyer: &Pubkey,
+    auction: &Pubkey,
+    now: i64,
+) -> Result<u8> {
+    let current_index = load_current_index_checked(instructions)?;
+    if current_index == 0 {
+        return Err(LaunchpadError::NotWhitelisted.into());
+    }
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    if ix.program_id != ed25519_program::ID {
+        return Err(LaunchpadError::NotWhitelisted.into());
+    }
+
+    // Exactly one signature whose key and message live in this instruction
+    let data = &ix.data;
+    if data.len() < HEADER_LEN + OFFSETS_LEN || data[0] != 1 {
+        return Err(LaunchpadError::InvalidAttestation.into());
+    }
+    let u16_at = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]) as usize;
+    let signature_ix = u16_at(HEADER_LEN + 2);
+    let pubkey_offset = u16_at(HEADER_LEN + 4);
+    let pubkey_ix = u16_at(HEADER_LEN + 6);
+    let message_offset = u16_at(HEADER_LEN + 8);
+    let message_len = u16_at(HEADER_LEN + 10);
+    let message_ix = u16_at(HEADER_LEN + 12);
+    let current = CURRENT_INSTRUCTION as usize;
+    if signature_ix != current || pubkey_ix != current || message_ix != current {
+        return Err(LaunchpadError::InvalidAttestation.into());
+    }
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(LaunchpadError::InvalidAttestation)?;
+    if pubkey != authority.as_ref() {
+        return Err(LaunchpadError::InvalidAttestation.into());
+    }
+
+    let message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(LaunchpadError::InvalidAttestation)?;
+    let attestation = KycAttestation::try_from_slice(message)
+        .map_err(|_| LaunchpadError::InvalidAttestation)?;
+    if attestation.buyer != *buyer || attestation.auction != *auction {
+        return Err(LaunchpadError::InvalidAttestation.into());
+    }
+    if (attestation.tier as usize) >= MAX_TIERS {
+        return Err(LaunchpadError::InvalidTier.into());
+    }
+    if now >= attestation.expiry {
+        return Err(LaunchpadError::AttestationExpired.into());
+    }
+    Ok(attestation.tier)
+}
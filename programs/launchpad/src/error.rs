@@ -40,4 +40,126 @@ pub enum LaunchpadError {
     AlreadyParticipated,
     #[msg("Invalid Sol for 1 ticket")]
     InvalidSolFor1ticket,
+    #[msg("Invalid Ticket Price")]
+    InvalidTicketPrice,
+    #[msg("Auction Already Started")]
+    AuctionAlreadyStarted,
+    #[msg("Invalid Payment Accounts")]
+    InvalidPaymentAccounts,
+    #[msg("Invalid Price Feed")]
+    InvalidPriceFeed,
+    #[msg("Invalid Price")]
+    InvalidPrice,
+    #[msg("Stale Price")]
+    StalePrice,
+    #[msg("Price Confidence Too Wide")]
+    PriceConfidenceTooWide,
+    #[msg("Invalid Fee")]
+    InvalidFee,
+    #[msg("Program Paused")]
+    ProgramPaused,
+    #[msg("Buys Paused")]
+    BuysPaused,
+    #[msg("Withdrawals Paused")]
+    WithdrawalsPaused,
+    #[msg("Whitelist Paused")]
+    WhitelistPaused,
+    #[msg("Invalid Pause Flags")]
+    InvalidPauseFlags,
+    #[msg("Invalid Destination")]
+    InvalidDestination,
+    #[msg("Invalid Tier")]
+    InvalidTier,
+    #[msg("Too Early For Buyer Tier")]
+    TierCooldown,
+    #[msg("Slot Purchase Limit Reached")]
+    SlotPurchaseLimit,
+    #[msg("Buyer Not Registered")]
+    NotRegistered,
+    #[msg("Registration Closed")]
+    RegistrationClosed,
+    #[msg("Invalid Registration Time")]
+    InvalidRegistrationTime,
+    #[msg("Invalid Attestation")]
+    InvalidAttestation,
+    #[msg("Attestation Expired")]
+    AttestationExpired,
+    #[msg("Invalid Gate")]
+    InvalidGate,
+    #[msg("Invalid Gate Accounts")]
+    InvalidGateAccounts,
+    #[msg("Gate Token Already Used")]
+    GateTokenUsed,
+    #[msg("Invalid Staking Config")]
+    InvalidStakingConfig,
+    #[msg("Stake Locked")]
+    StakeLocked,
+    #[msg("Insufficient Stake")]
+    InsufficientStake,
+    #[msg("Stake Unlocks Before Auction Ends")]
+    StakeNotLocked,
+    #[msg("Snapshot Closed")]
+    SnapshotClosed,
+    #[msg("Allocation Exhausted")]
+    AllocationExhausted,
+    #[msg("Invalid Referral")]
+    InvalidReferral,
+    #[msg("Nothing To Claim")]
+    NothingToClaim,
+    #[msg("Invalid Liquidity Params")]
+    InvalidLiquidityParams,
+    #[msg("Liquidity Not Added")]
+    LiquidityNotAdded,
+    #[msg("Insufficient Liquidity")]
+    InsufficientLiquidity,
+    #[msg("Slippage Exceeded")]
+    SlippageExceeded,
+    #[msg("LP Tokens Locked")]
+    LpLocked,
+    #[msg("Invalid Vesting Schedule")]
+    InvalidVestingSchedule,
+    #[msg("Invalid Milestones")]
+    InvalidMilestones,
+    #[msg("Invalid Milestone")]
+    InvalidMilestone,
+    #[msg("Escrow Active")]
+    EscrowActive,
+    #[msg("Escrow Accepts SOL Only")]
+    EscrowSolOnly,
+    #[msg("Vote Not Open")]
+    VoteNotOpen,
+    #[msg("Vote Not Ended")]
+    VoteNotEnded,
+    #[msg("Escrow Not Rejected")]
+    EscrowNotRejected,
+    #[msg("Invalid Token Account")]
+    InvalidTokenAccount,
+    #[msg("Insufficient Vault Balance")]
+    InsufficientVaultBalance,
+    #[msg("Invalid Auction Name")]
+    InvalidAuctionName,
+    #[msg("Invalid Metadata")]
+    InvalidMetadata,
+    #[msg("Invalid Buyer Registry")]
+    InvalidBuyerRegistry,
+    #[msg("Buyer Registry Full")]
+    BuyerRegistryFull,
+    #[msg("Auction Finalized")]
+    AuctionFinalized,
+    #[msg("Grace Period Not Over")]
+    GracePeriodNotOver,
+    #[msg("Invalid Grace Period")]
+    InvalidGracePeriod,
+    #[msg("Invalid Unsold Token Policy")]
+    InvalidUnsoldTokenPolicy,
+    #[msg("Mock Oracle Disabled")]
+    MockOracleDisabled,
+    #[msg("Buyer Registry Too Small")]
+    BuyerRegistryTooSmall,
+    #[msg("Payment Mint Mismatch")]
+    PaymentMintMismatch,
+    #[msg("Invalid Whitelist Accounts")]
+    InvalidWhitelistAccounts,
+    #[msg("Invalid Team Vesting")]
+    InvalidTeamVesting,
 }
@@ -0,0 +1,13 @@
+//! Events emitted by the program
+
+use crate::state::UnsoldTokenPolicy;
+use anchor_lang::prelude::*;
+
+/// The tokens left in the vault were handled by the auction's policy on settlement
+#[event]
+pub struct UnsoldTokensSettled {
+    pub auction: Pubkey,
+    pub policy: UnsoldTokenPolicy,
+    pub amount: u64,
+    pub token_account: Pubkey,  // that received them, default when burnt
+}
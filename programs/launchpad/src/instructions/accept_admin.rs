@@ -0,0 +1,26 @@
+use crate::{error::LaunchpadError, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Ensure that the admin is accepted by the proposed admin
+    if config.pending_admin == Pubkey::default() || ctx.accounts.new_admin.key() != config.pending_admin {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, TeamVesting},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    pub new_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"team_vesting", auction.key().as_ref()],
+        bump = team_vesting.bump,
+    )]
+    pub team_vesting: Option<Box<Account<'info, TeamVesting>>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Hand the auction over to its proposed owner, along with the proceeds and unsold
+/// destinations that still point at the previous owner. The team vesting, required
+/// once a team allocation is locked, is handed over the same way.
+pub fn handler(ctx: Context<AcceptOwner>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+
+    // Ensure that the ownership is accepted by the proposed owner
+    if auction.pending_owner == Pubkey::default() || ctx.accounts.new_owner.key() != auction.pending_owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Destinations left to the previous owner follow the ownership,
+    // so that finalizing doesn't pay an owner who has handed the auction over
+    let previous_owner = auction.owner;
+    auction.owner = auction.pending_owner;
+    auction.pending_owner = Pubkey::default();
+    if auction.proceeds_destination == previous_owner {
+        auction.proceeds_destination = auction.owner;
+    }
+    if auction.unsold_destination == previous_owner {
+        auction.unsold_destination = auction.owner;
+    }
+
+    if auction.team_allocation > 0 {
+        let team_vesting = ctx
+            .accounts
+            .team_vesting
+            .as_mut()
+            .ok_or(LaunchpadError::InvalidTeamVesting)?;
+        if team_vesting.beneficiary == previous_owner {
+            team_vesting.beneficiary = auction.owner;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, PaymentOption},
+    token_interface::{
+        create_associated_token_account, get_associated_token_address_with_program_id,
+        is_token_program,
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PaymentOptionParams {
+    pub price_per_ticket: u64, // in bid token base units
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentOption<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + std::mem::size_of::<PaymentOption>(),
+        seeds = [b"payment_option", auction.key().as_ref(), bid_token.key().as_ref()],
+        bump
+    )]
+    pub payment_option: Box<Account<'info, PaymentOption>>,
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &auction_vault.key(),
+            &bid_token.key(),
+            &token_program.key(),
+        )
+    )]
+    /// CHECK: address is the vault's associated token account, created if needed
+    pub auction_vault_bid_token_account: AccountInfo<'info>,
+    #[account(owner = token_program.key())]
+    /// CHECK: owned by the token program
+    pub bid_token: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program of the bid token
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<AddPaymentOption>, params: PaymentOptionParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+    let payment_option = &mut ctx.accounts.payment_option;
+
+    // Ensure that the payment option is added by the auction owner
+    if ctx.accounts.owner.key != &auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that prices can't be changed once buying has started
+    if ctx.accounts.clock.unix_timestamp >= auction.sale_start_time() {
+        return Err(LaunchpadError::AuctionAlreadyStarted.into());
+    }
+
+    // Ensure that a ticket has a price in this currency
+    if params.price_per_ticket == 0 {
+        return Err(LaunchpadError::InvalidTicketPrice.into());
+    }
+
+    create_associated_token_account(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.auction_vault_bid_token_account,
+        &ctx.accounts.auction_vault,
+        &ctx.accounts.bid_token,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
+
+    // Count the currencies, all of which must be settled together
+    if payment_option.auction == Pubkey::default() {
+        auction.payment_option_count = auction
+            .payment_option_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    payment_option.auction = auction.key();
+    payment_option.mint = ctx.accounts.bid_token.key();
+    payment_option.price_per_ticket = params.price_per_ticket;
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use crate::{error::LaunchpadError, state::auction::Auction};
-use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, Transfer},
+use crate::{
+    error::LaunchpadError,
+    state::{auction::Auction, Config},
+    token_interface::{
+        create_associated_token_account, get_associated_token_address_with_program_id,
+        is_token_account, is_token_program, load_mint, transfer_checked,
+    },
 };
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_spl::associated_token::AssociatedToken;
 
 #[derive(Accounts)]
 pub struct AddToken<'info> {
@@ -11,57 +15,98 @@ pub struct AddToken<'info> {
     pub owner: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"auction", auction.name.as_bytes()],
-        bump
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
     )]
     pub auction: Box<Account<'info, Auction>>,
     #[account(
         mut,
         seeds = [b"auction_vault", auction.key().as_ref()],
-        bump,
+        bump = auction.vault_bump,
     )]
     /// CHECK: seeds has been checked
     pub auction_vault: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(
         mut,
-        constraint = owner_auction_token_account.owner == owner.key(),
-        constraint = owner_auction_token_account.mint == auction_token.key()
+        constraint = is_token_account(&owner_auction_token_account, &owner.key(), &auction_token.key())
     )]
-    pub owner_auction_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: checked to be the owner's token account of the auction token
+    pub owner_auction_token_account: AccountInfo<'info>,
     #[account(
-        init_if_needed,
-        payer = owner,
-        associated_token::mint = auction_token,
-        associated_token::authority = auction_vault,
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &auction_vault.key(),
+            &auction_token.key(),
+            &token_program.key(),
+        )
     )]
-    pub auction_vault_token_account: Box<Account<'info, TokenAccount>>,
-    pub auction_token: Account<'info, Mint>,
+    /// CHECK: address is the vault's associated token account, created if needed
+    pub auction_vault_token_account: AccountInfo<'info>,
+    #[account(owner = token_program.key())]
+    /// CHECK: owned by the token program, loaded as a mint
+    pub auction_token: AccountInfo<'info>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program
+    pub token_program: AccountInfo<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub clock: Sysvar<'info, Clock>,
 }
 
+/// The auction token may belong to either token program. For mints with a transfer fee
+/// the owner pays the fee on top, so the vault holds the full allocation.
 pub fn handler(ctx: Context<AddToken>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
     let owner = &ctx.accounts.owner;
-    let from = &mut ctx.accounts.owner_auction_token_account;
-    let to = &mut ctx.accounts.auction_vault_token_account;
     let auction = &mut ctx.accounts.auction;
-    let token_program = ctx.accounts.token_program.to_account_info();
+
+    // Ensure that the tokens are added by the auction owner
+    if *owner.key != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
 
     // Ensure that pre_sale is enabled but not live yet
     if !(auction.pre_sale && (ctx.accounts.clock.unix_timestamp < auction.pre_sale_start_time)) {
         return  Err(LaunchpadError::PreSaleAlreadyStarted.into());
     }
 
-    let transfer = Transfer {
-        from: from.to_account_info(),
-        to: to.to_account_info(),
-        authority: owner.to_account_info(),
-    };
+    // Ensure that the auction keeps selling the mint it was first given
+    let auction_token = ctx.accounts.auction_token.key();
+    if auction.token_mint != Pubkey::default() && auction.token_mint != auction_token {
+        return Err(LaunchpadError::InvalidToken.into());
+    }
+    auction.token_mint = auction_token;
+
+    create_associated_token_account(
+        &owner.to_account_info(),
+        &ctx.accounts.auction_vault_token_account,
+        &ctx.accounts.auction_vault,
+        &ctx.accounts.auction_token,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
 
-    let ctx: CpiContext<'_, '_, '_, '_, _> = CpiContext::new(token_program, transfer);
-    anchor_spl::token::transfer(ctx, auction.tokens_in_pool * LAMPORTS_PER_SOL)?;
+    // The liquidity allocation is deposited along with the tokens on sale
+    let mint = load_mint(&ctx.accounts.auction_token)?;
+    let amount = (auction.tokens_in_pool + auction.liquidity_tokens) * LAMPORTS_PER_SOL;
+    transfer_checked(
+        &ctx.accounts.token_program,
+        &ctx.accounts.owner_auction_token_account,
+        &ctx.accounts.auction_token,
+        &ctx.accounts.auction_vault_token_account,
+        &owner.to_account_info(),
+        &[],
+        mint.gross_up(amount)?,
+        mint.decimals,
+    )?;
     Ok(())
 }
@@ -0,0 +1,67 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Escrow, PAUSE_WITHDRAWALS},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    pub approver: Signer<'info>,
+    #[account(mut, address = escrow.auction)]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.auction.as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", escrow.auction.as_ref()],
+        bump = escrow.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(mut, address = escrow.beneficiary)]
+    /// CHECK: address is the escrow's beneficiary
+    pub beneficiary: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, address = config.fee_treasury)]
+    /// CHECK: address is the configured fee treasury
+    pub fee_treasury: AccountInfo<'info>,
+}
+
+/// Approve the requested milestone, releasing its tranche, or reject it to refund buyers
+pub fn handler(ctx: Context<ApproveMilestone>, approve: bool) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    // Ensure that milestones are approved by the designated approver
+    if escrow.approver == Pubkey::default() || ctx.accounts.approver.key() != escrow.approver {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that the milestone has been requested
+    if escrow.vote_end == 0 || escrow.finished() {
+        return Err(LaunchpadError::InvalidMilestone.into());
+    }
+
+    if approve {
+        escrow.release(
+            &ctx.accounts.auction,
+            &ctx.accounts.auction_vault,
+            &ctx.accounts.beneficiary,
+            &ctx.accounts.fee_treasury,
+        )?;
+        ctx.accounts.auction.escrow_mode = !escrow.finished();
+    } else {
+        escrow.rejected = true;
+    }
+    Ok(())
+}
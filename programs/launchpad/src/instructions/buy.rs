@@ -0,0 +1,337 @@
+use crate::{
+    attestation::verify_attestation,
+    error::LaunchpadError,
+    payment::{NativeSol, PaymentAsset, SplToken},
+    state::{
+        buyer_tier, load_stake_allocation, track_referral, whitelisted_tier, Auction, Buyer,
+        BuyerRegistry, Config, PAUSE_BUYS,
+    },
+    token_gate::verify_token_gate,
+    token_interface::{is_token_account, is_token_program, load_mint, transfer_checked},
+};
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SalePhase {
+    PreSale,
+    Public,
+}
+
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 8 + std::mem::size_of::<Buyer>(),
+        payer = buyer,
+        seeds = [b"buyer", buyer.key().as_ref(), auction.key().as_ref()],
+        bump,
+    )]
+    pub buyer_pda: Box<Account<'info, Buyer>>,
+    #[account(mut)]
+    /// CHECK: loaded and checked against the referrer when there is one, any writable
+    /// account (e.g. the buyer) otherwise
+    pub referral: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        constraint = is_token_account(&auction_vault_token_account, &auction_vault.key(), &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by auction_vault
+    pub auction_vault_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&buyer_auction_token_account, &buyer.key(), &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by buyer
+    pub buyer_auction_token_account: AccountInfo<'info>,
+    #[account(owner = token_program.key())]
+    /// CHECK: owned by the token program, loaded as a mint
+    pub auction_token: AccountInfo<'info>,
+    #[account(
+        seeds = [b"whitelist", buyer.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    /// CHECK: seeds has been checked, may not exist for public buyers or buyers
+    /// with a KYC attestation
+    pub whitelist_pda: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"stake_allocation", auction.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    /// CHECK: seeds has been checked, may not exist for buyers that didn't stake
+    pub stake_allocation: AccountInfo<'info>,
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: address is the instructions sysvar
+    pub instructions: AccountInfo<'info>,
+    #[account(address = auction.price_feed)]
+    /// CHECK: address is the auction's feed, layout is checked when loading the price
+    pub price_feed: AccountInfo<'info>,
+    /// CHECK: loaded as a payment option of the auction, only when paying in SPL
+    pub payment_option: Option<AccountInfo<'info>>,
+    /// CHECK: checked against the payment option
+    pub bid_token: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    /// CHECK: checked to be a bid_token account owned by buyer
+    pub buyer_bid_token_account: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    /// CHECK: checked to be a bid_token account owned by auction_vault
+    pub auction_vault_bid_token_account: Option<AccountInfo<'info>>,
+    /// CHECK: checked to be the SPL Token or the Token-2022 program of the bid token
+    pub bid_token_program: Option<AccountInfo<'info>>,
+    #[account(mut, address = auction.buyer_registry)]
+    pub buyer_registry: Option<AccountLoader<'info, BuyerRegistry>>,
+    pub clock: Sysvar<'info, Clock>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program of the auction token
+    pub token_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy one ticket in the pre-sale or the public sale, paying in SOL or, when the
+/// payment option and bid token accounts are passed, in one of the accepted SPL tokens.
+/// Stakers buy all the tickets of their allocation at once in the pre-sale.
+///
+/// Auctions with a buyer registry also take it, to list the buyer in.
+///
+/// Buyers of auctions that require registration must have registered first, buyers
+/// of open auctions can buy straight away.
+///
+/// Pre-sale buyers without a stake allocation or whitelist entry pass their gating
+/// token accounts as `remaining_accounts` (see `verify_token_gate`), or a KYC
+/// attestation in the preceding ed25519 instruction.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+    phase: SalePhase,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
+    // Ensure that buys are not paused
+    ctx.accounts.config.check_active(PAUSE_BUYS)?;
+
+    let accounts = &ctx.accounts;
+    match (
+        &accounts.payment_option,
+        &accounts.bid_token,
+        &accounts.bid_token_program,
+        &accounts.buyer_bid_token_account,
+        &accounts.auction_vault_bid_token_account,
+    ) {
+        (None, None, None, None, None) => {
+            let asset = NativeSol {
+                auction_vault: accounts.auction_vault.clone(),
+                price_feed: accounts.price_feed.clone(),
+                system_program: accounts.system_program.to_account_info(),
+            };
+            buy_with(ctx, asset, phase, referrer)
+        }
+        (
+            Some(payment_option),
+            Some(bid_token),
+            Some(bid_token_program),
+            Some(buyer_bid_token_account),
+            Some(auction_vault_bid_token_account),
+        ) => {
+            let asset = SplToken::load(
+                accounts.auction.key(),
+                accounts.buyer.key(),
+                accounts.auction_vault.key(),
+                payment_option,
+                bid_token,
+                bid_token_program,
+                buyer_bid_token_account,
+                auction_vault_bid_token_account,
+            )?;
+            buy_with(ctx, asset, phase, referrer)
+        }
+        _ => Err(LaunchpadError::InvalidPaymentAccounts.into()),
+    }
+}
+
+fn buy_with<'info, A: PaymentAsset<'info>>(
+    ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+    mut asset: A,
+    phase: SalePhase,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let auction_vault: &AccountInfo<'_> = &ctx.accounts.auction_vault;
+    let buyer = &ctx.accounts.buyer;
+    let buyer_pda = &mut ctx.accounts.buyer_pda;
+    let buyer_key = buyer.key();
+    let auction_key = auction.key();
+    let clock = &ctx.accounts.clock;
+    let current_ts = clock.unix_timestamp;
+    let mut stake_allocation = match phase {
+        SalePhase::PreSale => load_stake_allocation(&ctx.accounts.stake_allocation, auction_key, buyer_key),
+        SalePhase::Public => None,
+    };
+
+    // Ensure that the buyer has not participated in the auction
+    // This is to restrict same user to participate in same auction multiple times
+    if buyer_pda.participate {
+        return Err(LaunchpadError::AlreadyParticipated.into());
+    }
+
+    // Ensure that the auction accepts the currency paid in
+    asset.check_accepted(auction)?;
+
+    // Ensure that the auction is enabled
+    if !auction.enabled {
+        return Err(LaunchpadError::InvalidAuction.into());
+    }
+
+    // Ensure that the buyer registered when the auction requires it
+    if auction.registration_required && buyer_pda.registered_at == 0 {
+        return Err(LaunchpadError::NotRegistered.into());
+    }
+    if buyer_pda.wallet == Pubkey::default() {
+        buyer_pda.wallet = buyer_key;
+        buyer_pda.auction = auction_key;
+    }
+
+    let mut tickets: u32 = 1;
+    let tier = match phase {
+        SalePhase::PreSale => {
+            // Ensure if the auction presale is enabled
+            if !auction.pre_sale {
+                return Err(LaunchpadError::PreSaleNotEnabled.into());
+            }
+
+            // Ensure presale is live
+            if !(current_ts > auction.pre_sale_start_time && current_ts < auction.pre_sale_end_time) {
+                return Err(LaunchpadError::InvalidPresaleTime.into());
+            }
+
+            // Ensure if the the buyer is whitelisted, by their stake, the owner, a gating token
+            // or a KYC attestation
+            match (stake_allocation.as_mut(), whitelisted_tier(&ctx.accounts.whitelist_pda)) {
+                (Some(allocation), _) => {
+                    tickets = allocation.take_tickets()?;
+                    allocation.tier
+                }
+                (None, Some(tier)) => tier,
+                (None, None) if !ctx.remaining_accounts.is_empty() => verify_token_gate(
+                    auction,
+                    auction_key,
+                    &buyer.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    ctx.remaining_accounts,
+                    ctx.program_id,
+                )?,
+                (None, None) if auction.kyc_authority != Pubkey::default() => verify_attestation(
+                    &ctx.accounts.instructions,
+                    &auction.kyc_authority,
+                    &buyer_key,
+                    &auction_key,
+                    current_ts,
+                )?,
+                (None, None) => return Err(LaunchpadError::NotWhitelisted.into()),
+            }
+        }
+        SalePhase::Public => {
+            // Ensure if the pre sale has ended
+            if auction.pre_sale && current_ts < auction.pre_sale_end_time {
+                return Err(LaunchpadError::PreSaleNotEnded.into());
+            }
+
+            // Ensure that the auction is live
+            if !(current_ts > auction.start_time && current_ts < auction.end_time) {
+                return Err(LaunchpadError::InvalidAuction.into());
+            }
+
+            buyer_tier(&ctx.accounts.whitelist_pda)
+        }
+    };
+
+    // Ensure that the anti-bot limits for the buyer's tier are respected
+    auction.check_bot_limits(tier, phase == SalePhase::PreSale, clock)?;
+
+    // Ensure there are enough tokens remaining for the buyer
+    let token_quantity = auction
+        .token_quantity_per_ticket
+        .checked_mul(tickets as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if auction.remaining_tokens < token_quantity {
+        return Err(LaunchpadError::InsufficientTokens.into());
+    }
+
+    // Generate auction seed
+    let auction_vault_seed: &[&[&[_]]] = &[&[
+        "auction_vault".as_bytes(),
+        auction_key.as_ref(),
+        &[auction.vault_bump],
+    ]];
+
+    // Perform the token transfer to the buyer
+    let auction_token = &ctx.accounts.auction_token;
+    transfer_checked(
+        &ctx.accounts.token_program,
+        &ctx.accounts.auction_vault_token_account,
+        auction_token,
+        &ctx.accounts.buyer_auction_token_account,
+        auction_vault,
+        auction_vault_seed,
+        token_quantity * LAMPORTS_PER_SOL,
+        load_mint(auction_token)?.decimals,
+    )?;
+
+    // Collect the price of the tickets from the buyer
+    let price = asset
+        .ticket_price(auction, clock)?
+        .checked_mul(tickets as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let received = asset.collect(&buyer.to_account_info(), price)?;
+
+    // Track the amount collected for withdrawal, less the referrer's reward
+    let referral_reward = track_referral(
+        &ctx.accounts.referral,
+        auction_key,
+        asset.mint(),
+        referrer,
+        buyer_key,
+        received,
+        auction.referral_bps,
+    )?;
+    asset.record(auction, buyer_pda, price, received, referral_reward)?;
+
+    // Update the remaining tokens in the auction
+    auction.remaining_tokens -= token_quantity;
+
+    // Update the buyer account
+    buyer_pda.participate = true;
+    buyer_pda.tickets += tickets;
+
+    // List the buyer in the auction's registry, if it keeps one
+    if auction.buyer_registry != Pubkey::default() {
+        ctx.accounts
+            .buyer_registry
+            .as_ref()
+            .ok_or(LaunchpadError::InvalidBuyerRegistry)?
+            .load_mut()?
+            .record(buyer_pda, asset.mint(), price)?;
+    }
+    if let Some(allocation) = stake_allocation {
+        allocation.exit(&crate::ID)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Buyer, Config, Escrow, PAUSE_WITHDRAWALS},
+    vault,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimEscrowRefund<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        seeds = [b"escrow", escrow.auction.as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        seeds = [b"buyer", buyer.key().as_ref(), escrow.auction.as_ref()],
+        bump,
+    )]
+    pub buyer_pda: Box<Account<'info, Buyer>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", escrow.auction.as_ref()],
+        bump = escrow.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+/// Refund the buyer's share of what is left in a rejected escrow
+pub fn handler(ctx: Context<ClaimEscrowRefund>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let escrow = &ctx.accounts.escrow;
+    let buyer_pda = &mut ctx.accounts.buyer_pda;
+    let auction_vault = &ctx.accounts.auction_vault;
+
+    // Ensure that a milestone was rejected
+    if !escrow.rejected {
+        return Err(LaunchpadError::EscrowNotRejected.into());
+    }
+
+    let refund = escrow.refund(buyer_pda.sol_paid);
+    if buyer_pda.refunded || refund == 0 {
+        return Err(LaunchpadError::NothingToClaim.into());
+    }
+
+    vault::pay_out(auction_vault, &ctx.accounts.buyer.to_account_info(), refund)?;
+
+    buyer_pda.refunded = true;
+    Ok(())
+}
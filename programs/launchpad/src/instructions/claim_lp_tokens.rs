@@ -0,0 +1,84 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, Pool},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer as transfer_spl, Mint, Token, TokenAccount, Transfer as Transfer_Spl},
+};
+
+#[derive(Accounts)]
+pub struct ClaimLpTokens<'info> {
+    #[account(mut)]
+    pub lp_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.auction.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(address = pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = pool,
+    )]
+    pub pool_lp_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = lp_owner,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_owner,
+    )]
+    pub owner_lp_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<ClaimLpTokens>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let pool = &mut ctx.accounts.pool;
+
+    // Ensure that the LP tokens are claimed by their owner
+    if ctx.accounts.lp_owner.key() != pool.lp_owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that the lock is over and the LP tokens are still held by the pool
+    if ctx.accounts.clock.unix_timestamp < pool.lp_unlock_time {
+        return Err(LaunchpadError::LpLocked.into());
+    }
+    if pool.lp_claimed {
+        return Err(LaunchpadError::NothingToClaim.into());
+    }
+
+    let auction_key = pool.auction;
+    let pool_seed: &[&[&[_]]] = &[&["pool".as_bytes(), auction_key.as_ref(), &[pool.bump]]];
+
+    let trans_spl = Transfer_Spl {
+        from: ctx.accounts.pool_lp_token_account.to_account_info(),
+        to: ctx.accounts.owner_lp_token_account.to_account_info(),
+        authority: pool.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        trans_spl,
+        pool_seed,
+    );
+    transfer_spl(cpi_ctx, ctx.accounts.pool_lp_token_account.amount)?;
+
+    pool.lp_claimed = true;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, PaymentOption, Referral, PAUSE_WITHDRAWALS},
+    token_interface::{is_token_program, load_mint, load_token_account, transfer_checked},
+    vault,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimReferralReward<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(mut, address = auction.proceeds_destination)]
+    /// CHECK: address is the proceeds destination, receives the vault's rent when it is closed
+    pub proceeds_destination: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"referral", auction.key().as_ref(), referrer.key().as_ref(), referral.mint.as_ref()],
+        bump,
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program of the bid token
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Rewards in a bid token are paid through `remaining_accounts`, passed as
+/// `[payment_option, auction_vault_bid_token_account, referrer_bid_token_account, bid_token]`.
+///
+/// The last SOL reward claimed after settlement closes the vault, as settlement does
+/// when no reward is owed, returning its rent to the proceeds destination.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimReferralReward<'info>>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let auction = &mut ctx.accounts.auction;
+    let auction_vault = &ctx.accounts.auction_vault;
+    let referral = &mut ctx.accounts.referral;
+
+    // Ensure that the auction has ended
+    if ctx.accounts.clock.unix_timestamp <= auction.end_time {
+        return Err(LaunchpadError::AuctionNotEnded.into());
+    }
+
+    let reward = referral.reward;
+    if reward == 0 {
+        return Err(LaunchpadError::NothingToClaim.into());
+    }
+
+    if referral.mint == Pubkey::default() {
+        auction.sol_referral_reserved = auction
+            .sol_referral_reserved
+            .checked_sub(reward)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        vault::pay_out(auction_vault, &ctx.accounts.referrer.to_account_info(), reward)?;
+
+        // Close the vault once the auction is settled and nothing is owed from it anymore,
+        // escrowed proceeds are still released from it by milestone
+        if auction.finalized_at != 0 && auction.sol_referral_reserved == 0 && !auction.escrow_mode {
+            vault::close(auction_vault, &ctx.accounts.proceeds_destination)?;
+        }
+    } else {
+        let [payment_option, vault_bid_token_account_info, referrer_bid_token_account_info, bid_token] =
+            ctx.remaining_accounts
+        else {
+            return Err(LaunchpadError::InvalidPaymentAccounts.into());
+        };
+        let mut payment_option: Account<PaymentOption> = Account::try_from(payment_option)?;
+        let vault_bid_token_account = load_token_account(vault_bid_token_account_info)?;
+        let referrer_bid_token_account = load_token_account(referrer_bid_token_account_info)?;
+
+        // Ensure that the payment accounts belong to this auction, currency and referrer
+        if payment_option.auction != auction.key()
+            || payment_option.mint != referral.mint
+            || vault_bid_token_account.owner != auction_vault.key()
+            || vault_bid_token_account.mint != referral.mint
+            || referrer_bid_token_account.owner != referral.referrer
+            || referrer_bid_token_account.mint != referral.mint
+            || bid_token.key() != referral.mint
+        {
+            return Err(LaunchpadError::InvalidPaymentAccounts.into());
+        }
+
+        // Generate auction seed
+        let auction_key = auction.key();
+        let auction_vault_seed: &[&[&[_]]] = &[&[
+            "auction_vault".as_bytes(),
+            auction_key.as_ref(),
+            &[auction.vault_bump],
+        ]];
+
+        transfer_checked(
+            &ctx.accounts.token_program,
+            vault_bid_token_account_info,
+            bid_token,
+            referrer_bid_token_account_info,
+            auction_vault,
+            auction_vault_seed,
+            reward,
+            load_mint(bid_token)?.decimals,
+        )?;
+
+        payment_option.referral_reserved = payment_option
+            .referral_reserved
+            .checked_sub(reward)
+            .ok_or(ProgramError::InvalidArgument)?;
+        payment_option.exit(ctx.program_id)?;
+    }
+
+    referral.reward = 0;
+    referral.claimed = referral
+        .claimed
+        .checked_add(reward)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(())
+}
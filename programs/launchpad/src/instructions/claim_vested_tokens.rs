@@ -0,0 +1,84 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, TeamVesting, PAUSE_WITHDRAWALS},
+    token_interface::{is_token_account, is_token_program, load_mint, transfer_checked},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimVestedTokens<'info> {
+    pub beneficiary: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"team_vesting", team_vesting.auction.as_ref()],
+        bump = team_vesting.bump,
+    )]
+    pub team_vesting: Box<Account<'info, TeamVesting>>,
+    #[account(
+        seeds = [b"team_vault", team_vesting.auction.as_ref()],
+        bump = team_vesting.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub team_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&team_vault_token_account, &team_vault.key(), &team_vesting.mint)
+    )]
+    /// CHECK: checked to be a vested token account owned by team_vault
+    pub team_vault_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&beneficiary_token_account, &beneficiary.key(), &team_vesting.mint)
+    )]
+    /// CHECK: checked to be a vested token account owned by beneficiary
+    pub beneficiary_token_account: AccountInfo<'info>,
+    #[account(address = team_vesting.mint, owner = token_program.key())]
+    /// CHECK: address is the vested mint
+    pub auction_token: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<ClaimVestedTokens>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let team_vesting = &mut ctx.accounts.team_vesting;
+
+    // Ensure that the tokens are claimed by the beneficiary
+    if ctx.accounts.beneficiary.key() != team_vesting.beneficiary {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    let claimable = team_vesting.vested(ctx.accounts.clock.unix_timestamp) - team_vesting.claimed;
+    if claimable == 0 {
+        return Err(LaunchpadError::NothingToClaim.into());
+    }
+
+    // Generate team vault seed
+    let auction_key = team_vesting.auction;
+    let team_vault_seed: &[&[&[_]]] =
+        &[&["team_vault".as_bytes(), auction_key.as_ref(), &[team_vesting.vault_bump]]];
+
+    let auction_token = &ctx.accounts.auction_token;
+    transfer_checked(
+        &ctx.accounts.token_program,
+        &ctx.accounts.team_vault_token_account,
+        auction_token,
+        &ctx.accounts.beneficiary_token_account,
+        &ctx.accounts.team_vault,
+        team_vault_seed,
+        claimable,
+        load_mint(auction_token)?.decimals,
+    )?;
+
+    team_vesting.claimed += claimable;
+    Ok(())
+}
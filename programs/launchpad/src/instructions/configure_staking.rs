@@ -0,0 +1,60 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, STAKE_TIERS},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StakingParams {
+    pub stake_mint: Pubkey,
+    pub lock_period: i64,  // in seconds
+    pub thresholds: [u64; STAKE_TIERS],  // minimum stake of each tier, descending, 0 if unused
+    pub tickets: [u32; STAKE_TIERS],  // pre-sale tickets of each tier
+}
+
+#[derive(Accounts)]
+pub struct ConfigureStaking<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    /// CHECK: seeds has been checked, holds the staked tokens
+    pub stake_vault: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<ConfigureStaking>, params: StakingParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Ensure that staking is configured by the protocol admin
+    if ctx.accounts.admin.key() != config.admin {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that the staked token is not swapped under existing stakes
+    if config.total_staked > 0 && params.stake_mint != config.stake_mint {
+        return Err(LaunchpadError::InvalidStakingConfig.into());
+    }
+
+    // Ensure that better tiers need a larger stake
+    let descending = params
+        .thresholds
+        .windows(2)
+        .all(|pair| pair[1] == 0 || pair[0] > pair[1]);
+    if params.lock_period < 0 || !descending {
+        return Err(LaunchpadError::InvalidStakingConfig.into());
+    }
+
+    config.stake_mint = params.stake_mint;
+    config.stake_lock_period = params.lock_period;
+    config.stake_thresholds = params.thresholds;
+    config.stake_tickets = params.tickets;
+    config.stake_vault_bump = *ctx.bumps.get("stake_vault").unwrap();
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::{
+    error::LaunchpadError,
+    settlement::Settlement,
+    state::{Auction, Config, UnsoldTokenPolicy, PAUSE_WITHDRAWALS},
+    token_interface::{is_token_account, is_token_program},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FinalizeAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(mut, address = auction.proceeds_destination)]
+    /// CHECK: address is the proceeds destination set at init
+    pub proceeds_destination: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&auction_vault_token_account, &auction_vault.key(), &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by auction_vault
+    pub auction_vault_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = auction.unsold_token_policy == UnsoldTokenPolicy::Burn
+            || is_token_account(&unsold_token_account, &auction.unsold_destination, &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by the unsold destination set at init,
+    /// unused when the unsold tokens are burnt
+    pub unsold_token_account: AccountInfo<'info>,
+    #[account(mut, owner = token_program.key())]
+    /// CHECK: owned by the token program, loaded as a mint, burnt from with Burn
+    pub auction_token: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, address = config.fee_treasury)]
+    /// CHECK: address is the configured fee treasury
+    pub fee_treasury: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", auction.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds has been checked, only loaded in escrow mode
+    pub escrow: AccountInfo<'info>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program
+    pub token_program: AccountInfo<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Settle an auction its creator hasn't withdrawn once the grace period after its end
+/// is over, so that buyers aren't left waiting on the creator. Anyone can call it.
+///
+/// Proceeds go to the proceeds destination set at init, and unsold tokens to the unsold
+/// destination unless the auction burns them, settled the same way as by `withdraw_funds`.
+/// Bid token proceeds are passed through `remaining_accounts` as for `withdraw_funds`,
+/// with the proceeds destination's bid token accounts.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeAuction<'info>>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let auction = &mut ctx.accounts.auction;
+    let now = ctx.accounts.clock.unix_timestamp;
+
+    // Ensure that the auction has not been finalized already
+    if auction.finalized_at != 0 {
+        return Err(LaunchpadError::AuctionFinalized.into());
+    }
+
+    // Ensure that the creator has had the grace period to withdraw
+    let finalizable_at = auction
+        .end_time
+        .checked_add(auction.grace_period)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if now <= finalizable_at {
+        return Err(LaunchpadError::GracePeriodNotOver.into());
+    }
+
+    // Ensure that the promised liquidity is seeded before the proceeds are taken
+    if auction.liquidity_bps > 0 && !auction.liquidity_added {
+        return Err(LaunchpadError::LiquidityNotAdded.into());
+    }
+
+    let settlement = Settlement {
+        auction_vault: &ctx.accounts.auction_vault,
+        auction_vault_token_account: &ctx.accounts.auction_vault_token_account,
+        auction_token: &ctx.accounts.auction_token,
+        token_program: &ctx.accounts.token_program,
+        destination: &ctx.accounts.proceeds_destination,
+        unsold_token_account: &ctx.accounts.unsold_token_account,
+        config: &ctx.accounts.config,
+        fee_treasury: &ctx.accounts.fee_treasury,
+        escrow: &ctx.accounts.escrow,
+        payment_accounts: ctx.remaining_accounts,
+    };
+    let auction_key = auction.key();
+    settlement.settle(auction, auction_key, now)
+}
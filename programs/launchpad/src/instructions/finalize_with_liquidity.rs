@@ -0,0 +1,207 @@
+use crate::{
+    error::LaunchpadError,
+    state::{initial_lp_supply, Auction, Config, Pool, MAX_FEE_BPS, PAUSE_WITHDRAWALS},
+    token_interface::{
+        create_associated_token_account, get_associated_token_address_with_program_id,
+        is_token_account, is_token_program, load_mint, transfer_checked,
+    },
+    vault,
+};
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct FinalizeWithLiquidity<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump = auction.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&auction_vault_token_account, &auction_vault.key(), &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by auction_vault
+    pub auction_vault_token_account: AccountInfo<'info>,
+    #[account(owner = auction_token_program.key())]
+    /// CHECK: owned by the auction token program, loaded as a mint
+    pub auction_token: AccountInfo<'info>,
+    #[account(
+        init,
+        space = 8 + std::mem::size_of::<Pool>(),
+        payer = payer,
+        seeds = [b"pool", auction.key().as_ref()],
+        bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &pool.key(),
+            &auction_token.key(),
+            &auction_token_program.key(),
+        )
+    )]
+    /// CHECK: address is the pool's associated token account, created in the handler
+    pub pool_token_account: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump,
+        mint::decimals = 9,
+        mint::authority = pool,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = pool,
+    )]
+    pub pool_lp_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, address = config.fee_treasury)]
+    /// CHECK: address is the configured fee treasury
+    pub fee_treasury: AccountInfo<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    #[account(constraint = is_token_program(auction_token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program of the auction token
+    pub auction_token_program: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Seed the auction's liquidity pool with `liquidity_bps` of the SOL proceeds and the
+/// `liquidity_tokens` allocation, locking the LP tokens until `lp_unlock_time`.
+/// The platform fee is taken from that share here, the rest pays it at settlement.
+/// The LP mint is always an SPL Token mint, whichever program the auction token uses.
+///
+/// Anyone can seed the pool once the grace period is over, so that `finalize_auction`
+/// isn't held up by an owner who never does. The LP tokens stay locked to the owner.
+pub fn handler(ctx: Context<FinalizeWithLiquidity>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let auction = &mut ctx.accounts.auction;
+    let auction_vault = &ctx.accounts.auction_vault;
+    let pool = &mut ctx.accounts.pool;
+
+    // Ensure that the auction has ended
+    let now = ctx.accounts.clock.unix_timestamp;
+    if now <= auction.end_time {
+        return Err(LaunchpadError::AuctionNotEnded.into());
+    }
+
+    // Ensure that anyone but the auction owner waits out the grace period
+    let finalizable_at = auction
+        .end_time
+        .checked_add(auction.grace_period)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if ctx.accounts.payer.key() != auction.owner && now <= finalizable_at {
+        return Err(LaunchpadError::GracePeriodNotOver.into());
+    }
+
+    // Ensure that the auction promised liquidity and hasn't added it yet
+    if auction.liquidity_bps == 0 || auction.liquidity_added {
+        return Err(LaunchpadError::InvalidLiquidityParams.into());
+    }
+
+    let sol_amount = ((auction.sol_collected as u128) * (auction.liquidity_bps as u128)
+        / (MAX_FEE_BPS as u128)) as u64;
+    let token_amount = auction.liquidity_tokens * LAMPORTS_PER_SOL;
+    auction.liquidity_added = true;
+
+    // Without SOL proceeds there is nothing to pair the tokens with,
+    // so they go back to the owner with the unsold tokens
+    if sol_amount == 0 {
+        auction.remaining_tokens += auction.liquidity_tokens;
+        return Ok(());
+    }
+
+    // Move the SOL side from the auction vault to the pool, less the platform fee,
+    // so that proceeds don't escape the fee through the pool
+    auction.sol_collected -= sol_amount;
+    let sol_fee = auction.fee(sol_amount)?;
+    let sol_amount = sol_amount - sol_fee;
+    vault::pay_out(auction_vault, &ctx.accounts.fee_treasury, sol_fee)?;
+    vault::pay_out(auction_vault, &pool.to_account_info(), sol_amount)?;
+
+    // Generate auction seed
+    let auction_key = auction.key();
+    let auction_vault_seed: &[&[&[_]]] = &[&[
+        "auction_vault".as_bytes(),
+        auction_key.as_ref(),
+        &[auction.vault_bump],
+    ]];
+
+    // Move the token side from the auction vault to the pool,
+    // which receives it less any transfer fee
+    create_associated_token_account(
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.pool_token_account,
+        &pool.to_account_info(),
+        &ctx.accounts.auction_token,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.auction_token_program,
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
+    let mint = load_mint(&ctx.accounts.auction_token)?;
+    transfer_checked(
+        &ctx.accounts.auction_token_program,
+        &ctx.accounts.auction_vault_token_account,
+        &ctx.accounts.auction_token,
+        &ctx.accounts.pool_token_account,
+        auction_vault,
+        auction_vault_seed,
+        token_amount,
+        mint.decimals,
+    )?;
+    let token_amount = mint.received(token_amount)?;
+
+    // Mint the LP tokens into the pool's own account, where they stay locked
+    let lp_supply = initial_lp_supply(sol_amount, token_amount);
+    pool.bump = *ctx.bumps.get("pool").unwrap();
+    let pool_seed: &[&[&[_]]] = &[&["pool".as_bytes(), auction_key.as_ref(), &[pool.bump]]];
+    let mint_lp = MintTo {
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        to: ctx.accounts.pool_lp_token_account.to_account_info(),
+        authority: pool.to_account_info(),
+    };
+    let cpi_ctx: CpiContext<'_, '_, '_, '_, _> = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        mint_lp,
+        pool_seed,
+    );
+    mint_to(cpi_ctx, lp_supply)?;
+
+    pool.auction = auction_key;
+    pool.token_mint = ctx.accounts.auction_token.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.sol_reserve = sol_amount;
+    pool.token_reserve = token_amount;
+    pool.lp_supply = lp_supply;
+    pool.lp_owner = auction.owner;
+    pool.lp_unlock_time = auction.lp_unlock_time;
+    pool.lp_claimed = false;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Delegate, DelegateRole},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GrantRoleParams {
+    pub delegate: Pubkey,
+    pub role: DelegateRole,
+    pub destination: Pubkey,  // only used by the Withdrawer role
+}
+
+#[derive(Accounts)]
+#[instruction(params: GrantRoleParams)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + std::mem::size_of::<Delegate>(),
+        seeds = [b"delegate", auction.key().as_ref(), params.delegate.as_ref(), &[params.role as u8]],
+        bump
+    )]
+    pub delegate_pda: Box<Account<'info, Delegate>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &ctx.accounts.auction;
+    let delegate_pda = &mut ctx.accounts.delegate_pda;
+
+    // Ensure that roles are granted by the auction owner
+    if ctx.accounts.owner.key() != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that a withdrawer has a fixed destination for the funds
+    if params.role == DelegateRole::Withdrawer && params.destination == Pubkey::default() {
+        return Err(LaunchpadError::InvalidDestination.into());
+    }
+
+    delegate_pda.auction = auction.key();
+    delegate_pda.delegate = params.delegate;
+    delegate_pda.role = params.role;
+    delegate_pda.destination = params.destination;
+    delegate_pda.granted_by = auction.owner;
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::{state::auction::Auction, error::LaunchpadError};
+use anchor_lang::system_program::{transfer as transfer_sol, Transfer as Transfer_Sol};
+use crate::{state::{auction::Auction, AuctionMetadata, Config, GateKind, AUCTION_VERSION, MAX_FEE_BPS, MAX_NAME_LEN, MAX_TIERS, UnsoldTokenPolicy}, error::LaunchpadError};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitAuctionParams {
-    pub name: String,
+    pub name: String,  // at most MAX_NAME_LEN bytes
     pub enabled: bool,
     pub fixed_amount: bool,
     pub start_time: i64,
@@ -15,23 +16,51 @@ pub struct InitAuctionParams {
     pub tokens_in_pool: u64,  // pool of total tokens
     pub token_quantity_per_ticket: u64,  // no. of tokens in one ticket
     pub funding_demand: u64, // in SOL (return on investment)
+    pub usd_denominated: bool,
+    pub usd_ticket_price: u64,  // in USD with 6 decimals
+    pub price_feed: Pubkey,
+    pub max_price_age: i64,  // in seconds
+    pub max_price_confidence_bps: u16,
+    pub tier_delays: [u32; MAX_TIERS],  // seconds after the sale starts before each tier can buy
+    pub max_buys_per_slot: u32,  // 0 for no limit
+    pub registration_required: bool,
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub kyc_authority: Pubkey,  // default to whitelist with PDAs only
+    pub gate_kind: GateKind,
+    pub gate_mint: Pubkey,
+    pub gate_min_amount: u64,
+    pub gate_tier: u8,
+    pub referral_bps: u16,
+    pub liquidity_bps: u16,  // 0 to withdraw all proceeds
+    pub liquidity_tokens: u64,
+    pub lp_unlock_time: i64,
+    pub proceeds_destination: Pubkey,  // default to the owner
+    pub unsold_destination: Pubkey,  // default to the owner, the treasury with TransferTo
+    pub grace_period: i64,  // in seconds after end_time
+    pub unsold_token_policy: UnsoldTokenPolicy,
 }
 
 #[derive(Accounts)]
-#[instruction(params: InitAuctionParams)]
 pub struct InitAuction<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     #[account(
-        init_if_needed,   
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        init,
         payer = owner,
-        space = 8 + std::mem::size_of::<Auction>(),
-        seeds = [b"auction", params.name.as_bytes()],
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [b"auction", config.auction_count.to_le_bytes().as_ref()],
         bump
     )]
     pub auction: Box<Account<'info, Auction>>,
     #[account(
-        init_if_needed,
+        init,
         payer = owner,
         space = 0,
         seeds = [b"auction_vault", auction.key().as_ref()],
@@ -39,12 +68,33 @@ pub struct InitAuction<'info> {
     )]
     /// CHECK: seeds has been checked
     pub auction_vault: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + AuctionMetadata::BASE_SPACE,
+        seeds = [b"metadata", auction.key().as_ref()],
+        bump
+    )]
+    pub metadata: Box<Account<'info, AuctionMetadata>>,
+    #[account(mut, address = config.fee_treasury)]
+    /// CHECK: address is the configured fee treasury
+    pub fee_treasury: AccountInfo<'info>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
 }
 
+/// Create an auction under the next id of `Config.auction_count`
 pub fn handler(ctx: Context<InitAuction>, params: InitAuctionParams) -> Result<()> {
     let auction = &mut ctx.accounts.auction;
+    let config = &mut ctx.accounts.config;
+
+    // Ensure that the program is not paused
+    config.check_active(0)?;
+
+    // Ensure that the display name is set and fits in the account
+    if params.name.is_empty() || params.name.len() > MAX_NAME_LEN {
+        return Err(LaunchpadError::InvalidAuctionName.into());
+    }
 
     // Ensure auction end time is greater than auction start time
     if params.start_time >= params.end_time {
@@ -61,7 +111,79 @@ pub fn handler(ctx: Context<InitAuction>, params: InitAuctionParams) -> Result<(
         return Err(LaunchpadError::InvalidPresaleTime.into());
     }
 
+    // Ensure that registration closes before buying opens
+    let sale_start_time = if params.pre_sale { params.pre_sale_start_time } else { params.start_time };
+    if params.registration_required
+        && (params.registration_start >= params.registration_end
+            || params.registration_end > sale_start_time)
+    {
+        return Err(LaunchpadError::InvalidRegistrationTime.into());
+    }
+
+    // Ensure that token gated auctions have a gating mint
+    if params.gate_kind != GateKind::None
+        && (params.gate_mint == Pubkey::default()
+            || (params.gate_tier as usize) >= MAX_TIERS
+            || (params.gate_kind == GateKind::SplBalance && params.gate_min_amount == 0))
+    {
+        return Err(LaunchpadError::InvalidGate.into());
+    }
+
+    // Ensure that the referral reward is at most 100%
+    if params.referral_bps > MAX_FEE_BPS {
+        return Err(LaunchpadError::InvalidFee.into());
+    }
+
+    // Ensure that the liquidity pool gets both sides and is locked past the auction
+    if params.liquidity_bps > MAX_FEE_BPS
+        || (params.liquidity_bps > 0
+            && (params.liquidity_tokens == 0 || params.lp_unlock_time <= params.end_time))
+    {
+        return Err(LaunchpadError::InvalidLiquidityParams.into());
+    }
+
+    // Ensure that usd denominated auctions have a usable price feed and ticket price
+    if params.usd_denominated
+        && (params.price_feed == Pubkey::default()
+            || params.usd_ticket_price == 0
+            || params.max_price_age <= 0)
+    {
+        return Err(LaunchpadError::InvalidPriceFeed.into());
+    }
+
+    // Ensure that the owner has time to withdraw before anyone can finalize
+    if params.grace_period < 0 {
+        return Err(LaunchpadError::InvalidGracePeriod.into());
+    }
+
+    // Ensure that unsold tokens are not transferred to a treasury that was left out
+    if params.unsold_token_policy == UnsoldTokenPolicy::TransferTo
+        && params.unsold_destination == Pubkey::default()
+    {
+        return Err(LaunchpadError::InvalidUnsoldTokenPolicy.into());
+    }
+
+    // Charge the platform listing fee
+    if config.listing_fee > 0 {
+        let trns_sol = Transfer_Sol {
+            from: ctx.accounts.owner.to_account_info(),
+            to: ctx.accounts.fee_treasury.to_account_info(),
+        };
+        let ctx_sol: CpiContext<'_, '_, '_, '_, _> =
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), trns_sol);
+        transfer_sol(ctx_sol, config.listing_fee)?;
+    }
+
+    auction.id = config.auction_count;
+    auction.version = AUCTION_VERSION;
+    auction.fee_bps = config.fee_bps;
+    config.auction_count = config
+        .auction_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
     auction.owner = *ctx.accounts.owner.key;
+    auction.pending_owner = Pubkey::default();
     auction.name = params.name;
     auction.enabled = params.enabled;
     auction.fixed_amount = params.fixed_amount;
@@ -75,5 +197,49 @@ pub fn handler(ctx: Context<InitAuction>, params: InitAuctionParams) -> Result<(
     auction.remaining_tokens = params.tokens_in_pool;
     auction.token_quantity_per_ticket = params.token_quantity_per_ticket;
     auction.funding_demand = params.funding_demand;
+    auction.usd_denominated = params.usd_denominated;
+    auction.usd_ticket_price = params.usd_ticket_price;
+    auction.price_feed = params.price_feed;
+    auction.max_price_age = params.max_price_age;
+    auction.max_price_confidence_bps = params.max_price_confidence_bps;
+    auction.tier_delays = params.tier_delays;
+    auction.max_buys_per_slot = params.max_buys_per_slot;
+    auction.last_buy_slot = 0;
+    auction.buys_in_slot = 0;
+    auction.registration_required = params.registration_required;
+    auction.registration_start = params.registration_start;
+    auction.registration_end = params.registration_end;
+    auction.registered_count = 0;
+    auction.kyc_authority = params.kyc_authority;
+    auction.gate_kind = params.gate_kind;
+    auction.gate_mint = params.gate_mint;
+    auction.gate_min_amount = params.gate_min_amount;
+    auction.gate_tier = params.gate_tier;
+    auction.referral_bps = params.referral_bps;
+    auction.liquidity_bps = params.liquidity_bps;
+    auction.liquidity_tokens = if params.liquidity_bps > 0 { params.liquidity_tokens } else { 0 };
+    auction.lp_unlock_time = params.lp_unlock_time;
+    auction.liquidity_added = false;
+    auction.team_allocation = 0;
+    auction.sol_paid_total = 0;
+    auction.proceeds_destination = if params.proceeds_destination == Pubkey::default() {
+        auction.owner
+    } else {
+        params.proceeds_destination
+    };
+    auction.unsold_destination = if params.unsold_destination == Pubkey::default() {
+        auction.owner
+    } else {
+        params.unsold_destination
+    };
+    auction.grace_period = params.grace_period;
+    auction.unsold_token_policy = params.unsold_token_policy;
+    auction.bump = *ctx.bumps.get("auction").unwrap();
+    auction.vault_bump = *ctx.bumps.get("auction_vault").unwrap();
+
+    // Metadata starts empty and is filled in by set_metadata
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.auction = auction.key();
+    metadata.bump = *ctx.bumps.get("metadata").unwrap();
     Ok(())
 }
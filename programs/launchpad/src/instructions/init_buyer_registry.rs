@@ -0,0 +1,59 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, BuyerRegistry, Config, REGISTRY_CAPACITY},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitBuyerRegistry<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    // Too large to be created by the program, the client creates it owned by the program
+    #[account(zero)]
+    pub buyer_registry: AccountLoader<'info, BuyerRegistry>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Attach a registry that every buy of the auction is listed in
+pub fn handler(ctx: Context<InitBuyerRegistry>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+
+    // Ensure that the registry is attached by the auction owner
+    if ctx.accounts.owner.key() != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that the registry lists every buyer
+    if ctx.accounts.clock.unix_timestamp >= auction.sale_start_time() {
+        return Err(LaunchpadError::AuctionAlreadyStarted.into());
+    }
+
+    // Ensure that the registry has an entry for every buyer the auction can have
+    let max_tickets = auction.tokens_in_pool / auction.token_quantity_per_ticket;
+    if max_tickets > REGISTRY_CAPACITY as u64 {
+        return Err(LaunchpadError::BuyerRegistryTooSmall.into());
+    }
+
+    // Ensure that an auction keeps a single registry
+    if auction.buyer_registry != Pubkey::default() {
+        return Err(LaunchpadError::InvalidBuyerRegistry.into());
+    }
+
+    let mut registry = ctx.accounts.buyer_registry.load_init()?;
+    registry.auction = auction.key();
+    auction.buyer_registry = ctx.accounts.buyer_registry.key();
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Escrow, MAX_FEE_BPS, MAX_MILESTONES},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EscrowParams {
+    pub approver: Pubkey,  // default to let buyers vote on milestones
+    pub vote_period: i64,  // in seconds, for buyer votes
+    pub milestone_count: u8,
+    pub tranche_bps: [u16; MAX_MILESTONES],  // adding up to 100% over milestone_count
+}
+
+#[derive(Accounts)]
+pub struct InitEscrow<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        init_if_needed,
+        space = 8 + std::mem::size_of::<Escrow>(),
+        payer = owner,
+        seeds = [b"escrow", auction.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitEscrow>, params: EscrowParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+
+    // Ensure that the escrow is set up by the auction owner
+    if ctx.accounts.owner.key() != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that buyers know about the escrow before buying opens
+    if ctx.accounts.clock.unix_timestamp >= auction.sale_start_time() {
+        return Err(LaunchpadError::AuctionAlreadyStarted.into());
+    }
+
+    // Ensure that the tranches release the whole escrow and that someone can approve them
+    let count = params.milestone_count as usize;
+    let tranches = params.tranche_bps.get(..count).unwrap_or_default();
+    let total_bps: u32 = tranches.iter().map(|&bps| bps as u32).sum();
+    if count == 0
+        || count > MAX_MILESTONES
+        || tranches.contains(&0)
+        || total_bps != MAX_FEE_BPS as u32
+        || (params.approver == Pubkey::default() && params.vote_period <= 0)
+    {
+        return Err(LaunchpadError::InvalidMilestones.into());
+    }
+
+    // Ensure that the proceeds are not also promised to a liquidity pool
+    if auction.liquidity_bps > 0 {
+        return Err(LaunchpadError::InvalidMilestones.into());
+    }
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.set_inner(Escrow {
+        auction: auction.key(),
+        approver: params.approver,
+        vote_period: params.vote_period,
+        tranche_bps: params.tranche_bps,
+        milestone_count: params.milestone_count,
+        bump: *ctx.bumps.get("escrow").unwrap(),
+        vault_bump: auction.vault_bump,
+        ..Default::default()
+    });
+
+    auction.escrow_mode = true;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{
+    error::LaunchpadError,
+    program::Launchpad,
+    state::{Config, MAX_FEE_BPS},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigParams {
+    pub fee_bps: u16,
+    pub fee_treasury: Pubkey,
+    pub listing_fee: u64,  // in lamports
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<Config>(),
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Launchpad>,
+    // Only the upgrade authority of the program can create the config
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ LaunchpadError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    // Ensure that the fee is at most 100%
+    if params.fee_bps > MAX_FEE_BPS {
+        return Err(LaunchpadError::InvalidFee.into());
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.fee_bps = params.fee_bps;
+    config.fee_treasury = params.fee_treasury;
+    config.paused = false;
+    config.listing_fee = params.listing_fee;
+    config.pause_flags = 0;
+    config.pending_admin = Pubkey::default();
+    config.bump = *ctx.bumps.get("config").unwrap();
+    Ok(())
+}
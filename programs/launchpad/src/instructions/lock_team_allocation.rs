@@ -0,0 +1,141 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, TeamVesting},
+    token_interface::{
+        create_associated_token_account, get_associated_token_address_with_program_id,
+        is_token_account, is_token_program, load_mint, transfer_checked,
+    },
+};
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_spl::associated_token::AssociatedToken;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TeamAllocationParams {
+    pub amount: u64,  // no. of tokens, like tokens_in_pool
+    pub cliff_time: i64,
+    pub end_time: i64,  // fully vested from this time
+}
+
+#[derive(Accounts)]
+pub struct LockTeamAllocation<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        init,
+        space = 8 + std::mem::size_of::<TeamVesting>(),
+        payer = owner,
+        seeds = [b"team_vesting", auction.key().as_ref()],
+        bump,
+    )]
+    pub team_vesting: Box<Account<'info, TeamVesting>>,
+    #[account(
+        seeds = [b"team_vault", auction.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub team_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&owner_auction_token_account, &owner.key(), &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by owner
+    pub owner_auction_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &team_vault.key(),
+            &auction_token.key(),
+            &token_program.key(),
+        )
+    )]
+    /// CHECK: address is the team vault's associated token account, created if needed
+    pub team_vault_token_account: AccountInfo<'info>,
+    #[account(address = auction.token_mint, owner = token_program.key())]
+    /// CHECK: address is the mint on sale, stored by add_token
+    pub auction_token: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Lock `amount` of the tokens on sale in the team vault, vesting to the auction owner.
+/// The mint on sale is the one deposited by `add_token`, which has to come first.
+pub fn handler(ctx: Context<LockTeamAllocation>, params: TeamAllocationParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+
+    // Ensure that the allocation is locked by the auction owner
+    if ctx.accounts.owner.key() != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that buyers can see the lock before buying opens
+    if ctx.accounts.clock.unix_timestamp >= auction.sale_start_time() {
+        return Err(LaunchpadError::AuctionAlreadyStarted.into());
+    }
+
+    // Ensure that vesting starts at the end of the sale and the cliff is within it
+    if params.amount == 0
+        || params.cliff_time < auction.end_time
+        || params.end_time <= auction.end_time
+        || params.cliff_time > params.end_time
+    {
+        return Err(LaunchpadError::InvalidVestingSchedule.into());
+    }
+
+    create_associated_token_account(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.team_vault_token_account,
+        &ctx.accounts.team_vault,
+        &ctx.accounts.auction_token,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program.to_account_info(),
+    )?;
+
+    // The owner pays any transfer fee, so the vault holds the whole allocation
+    let amount = params.amount * LAMPORTS_PER_SOL;
+    let mint = load_mint(&ctx.accounts.auction_token)?;
+    transfer_checked(
+        &ctx.accounts.token_program,
+        &ctx.accounts.owner_auction_token_account,
+        &ctx.accounts.auction_token,
+        &ctx.accounts.team_vault_token_account,
+        &ctx.accounts.owner.to_account_info(),
+        &[],
+        mint.gross_up(amount)?,
+        mint.decimals,
+    )?;
+
+    let team_vesting = &mut ctx.accounts.team_vesting;
+    team_vesting.auction = auction.key();
+    team_vesting.beneficiary = auction.owner;
+    team_vesting.mint = ctx.accounts.auction_token.key();
+    team_vesting.total = amount;
+    team_vesting.claimed = 0;
+    team_vesting.start_time = auction.end_time;
+    team_vesting.cliff_time = params.cliff_time;
+    team_vesting.end_time = params.end_time;
+    team_vesting.bump = *ctx.bumps.get("team_vesting").unwrap();
+    team_vesting.vault_bump = *ctx.bumps.get("team_vault").unwrap();
+
+    auction.team_allocation = params.amount;
+    Ok(())
+}
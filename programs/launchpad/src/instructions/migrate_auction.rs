@@ -0,0 +1,146 @@
+use crate::{
+    error::LaunchpadError,
+    state::{whitelist::Whitelist, Auction, AuctionMetadata, Config, AUCTION_VERSION, LEGACY_WHITELIST_LEN},
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct MigrateAuction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, owner = crate::ID)]
+    /// CHECK: may be too small to deserialize until it is resized, checked to be an
+    /// auction PDA once loaded
+    pub auction: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AuctionMetadata::BASE_SPACE,
+        seeds = [b"metadata", auction.key().as_ref()],
+        bump
+    )]
+    pub metadata: Box<Account<'info, AuctionMetadata>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize an auction created by an older program version to the current layout and
+/// bring it to `AUCTION_VERSION`. Anyone can migrate, paying for the extra rent.
+///
+/// Auctions created before ids were seeded by their name and have no id or bumps
+/// stored. They keep their name-seeded address and are assigned the next id.
+///
+/// Auctions created before metadata get an empty `AuctionMetadata`. Whitelist PDAs of
+/// the auction created before tiers are passed as `[whitelist_pda, whitelist_user]`
+/// pairs in `remaining_accounts` and grown to the current layout, in the first tier.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, MigrateAuction<'info>>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction_info = &ctx.accounts.auction;
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // Grow the account to the current layout, the new bytes are zeroed
+    grow(auction_info, 8 + Auction::INIT_SPACE, &payer, &system_program)?;
+
+    let mut auction: Account<Auction> = Account::try_from(auction_info)?;
+
+    // Ensure that the account is the auction PDA of its id, or of its name before ids
+    let auction_key = Pubkey::create_program_address(
+        &[b"auction", auction.pda_seed().as_ref(), &[auction.bump]],
+        ctx.program_id,
+    );
+    if auction_key != Ok(auction_info.key()) {
+        if auction.version != 0 {
+            return Err(LaunchpadError::InvalidAuction.into());
+        }
+        let (auction_key, bump) =
+            Pubkey::find_program_address(&[b"auction", auction.name.as_bytes()], ctx.program_id);
+        if auction_key != auction_info.key() {
+            return Err(LaunchpadError::InvalidAuction.into());
+        }
+        let (_, vault_bump) = Pubkey::find_program_address(
+            &[b"auction_vault", auction_key.as_ref()],
+            ctx.program_id,
+        );
+
+        let config = &mut ctx.accounts.config;
+        auction.name_seeded = true;
+        auction.bump = bump;
+        auction.vault_bump = vault_bump;
+        auction.id = config.auction_count;
+        config.auction_count = config
+            .auction_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    if auction.version < AUCTION_VERSION {
+        auction.migrate(Clock::get()?.unix_timestamp, ctx.accounts.config.fee_bps);
+        auction.exit(&crate::ID)?;
+    }
+
+    // Metadata starts empty and is filled in by set_metadata
+    let metadata = &mut ctx.accounts.metadata;
+    if metadata.auction == Pubkey::default() {
+        metadata.auction = auction_info.key();
+        metadata.bump = *ctx.bumps.get("metadata").unwrap();
+    }
+
+    // Ensure that every whitelist PDA is passed with its user and belongs to the auction
+    let whitelist_accounts = ctx.remaining_accounts.chunks_exact(2);
+    if !whitelist_accounts.remainder().is_empty() {
+        return Err(LaunchpadError::InvalidWhitelistAccounts.into());
+    }
+    for accounts in whitelist_accounts {
+        let (whitelist, whitelist_user) = (&accounts[0], &accounts[1]);
+        let (whitelist_key, _) = Pubkey::find_program_address(
+            &[b"whitelist", whitelist_user.key().as_ref(), auction_info.key().as_ref()],
+            ctx.program_id,
+        );
+        if whitelist.key() != whitelist_key || whitelist.owner != ctx.program_id {
+            return Err(LaunchpadError::InvalidWhitelistAccounts.into());
+        }
+
+        // The zeroed tier byte puts the user in the first tier
+        if whitelist.data_len() == LEGACY_WHITELIST_LEN {
+            grow(whitelist, 8 + std::mem::size_of::<Whitelist>(), &payer, &system_program)?;
+        }
+    }
+    Ok(())
+}
+
+/// Resize `account` to `space` bytes if it is smaller, with `payer` topping up its rent.
+/// The new bytes are zeroed.
+fn grow<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+    let top_up = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if top_up > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        transfer(cpi_ctx, top_up)?;
+    }
+    account.realloc(space, true)?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::state::Config;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    /// CHECK: seeds has been checked, may be too small to deserialize until it is resized
+    pub config: AccountInfo<'info>,
+    #[account(
+        seeds = [b"stake_vault"],
+        bump,
+    )]
+    /// CHECK: seeds has been checked, only its bump is stored
+    pub stake_vault: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize a config created by an older program version to the current layout, and
+/// fill in the fields that are derived rather than configured, such as the bumps. Anyone can migrate,
+/// paying for the extra rent.
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = &ctx.accounts.config;
+    let space = 8 + std::mem::size_of::<Config>();
+
+    // Grow the account to the current layout, the new bytes are zeroed
+    if config_info.data_len() < space {
+        let top_up = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(config_info.lamports());
+        if top_up > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: config_info.clone(),
+                },
+            );
+            transfer(cpi_ctx, top_up)?;
+        }
+        config_info.realloc(space, true)?;
+    }
+
+    let mut config: Account<Config> = Account::try_from(config_info)?;
+    config.stake_vault_bump = *ctx.bumps.get("stake_vault").unwrap();
+    config.bump = *ctx.bumps.get("config").unwrap();
+    config.exit(&crate::ID)
+}
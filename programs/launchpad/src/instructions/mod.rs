@@ -1,17 +1,83 @@
+pub mod accept_admin;
+pub mod accept_owner;
+pub mod add_payment_option;
 pub mod add_token;
-pub mod buy_token_using_sol;
-pub mod buy_token_using_spl;
+pub mod approve_milestone;
+pub mod buy;
+pub mod claim_escrow_refund;
+pub mod claim_lp_tokens;
+pub mod claim_referral_reward;
+pub mod claim_vested_tokens;
+pub mod configure_staking;
+pub mod finalize_auction;
+pub mod finalize_with_liquidity;
+pub mod grant_role;
 pub mod init_auction;
-pub mod pre_sale_buy_using_spl;
-pub mod pre_sale_buy_using_sol;
+pub mod init_buyer_registry;
+pub mod init_escrow;
+pub mod initialize_config;
+pub mod lock_team_allocation;
+pub mod migrate_auction;
+pub mod migrate_config;
+pub mod propose_admin;
+pub mod propose_owner;
+pub mod register;
+pub mod register_referrer;
+pub mod remove_liquidity;
+pub mod request_milestone;
+pub mod resolve_milestone_vote;
+pub mod revoke_role;
+pub mod set_auction_enabled;
+pub mod set_metadata;
+pub mod set_mock_price;
+pub mod set_pause;
+pub mod snapshot_stake;
+pub mod stake;
+pub mod swap;
+pub mod unstake;
+pub mod update_config;
+pub mod vote_milestone;
 pub mod whitelist;
 pub mod withdraw_funds;
 
+pub use accept_admin::*;
+pub use accept_owner::*;
+pub use add_payment_option::*;
 pub use add_token::*;
-pub use buy_token_using_sol::*;
-pub use buy_token_using_spl::*;
+pub use approve_milestone::*;
+pub use buy::*;
+pub use claim_escrow_refund::*;
+pub use claim_lp_tokens::*;
+pub use claim_referral_reward::*;
+pub use claim_vested_tokens::*;
+pub use configure_staking::*;
+pub use finalize_auction::*;
+pub use finalize_with_liquidity::*;
+pub use grant_role::*;
 pub use init_auction::*;
-pub use pre_sale_buy_using_spl::*;
-pub use pre_sale_buy_using_sol::*;
+pub use init_buyer_registry::*;
+pub use init_escrow::*;
+pub use initialize_config::*;
+pub use lock_team_allocation::*;
+pub use migrate_auction::*;
+pub use migrate_config::*;
+pub use propose_admin::*;
+pub use propose_owner::*;
+pub use register::*;
+pub use register_referrer::*;
+pub use remove_liquidity::*;
+pub use request_milestone::*;
+pub use resolve_milestone_vote::*;
+pub use revoke_role::*;
+pub use set_auction_enabled::*;
+pub use set_metadata::*;
+pub use set_mock_price::*;
+pub use set_pause::*;
+pub use snapshot_stake::*;
+pub use stake::*;
+pub use swap::*;
+pub use unstake::*;
+pub use update_config::*;
+pub use vote_milestone::*;
 pub use whitelist::*;
-pub use withdraw_funds::*;
+pub use withdraw_funds::*;
\ No newline at end of file
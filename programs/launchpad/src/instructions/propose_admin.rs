@@ -0,0 +1,26 @@
+use crate::{error::LaunchpadError, state::Config};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Ensure that the new admin is proposed by the current admin
+    if ctx.accounts.admin.key() != config.admin {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // The admin only changes once the new admin accepts
+    config.pending_admin = new_admin;
+    Ok(())
+}
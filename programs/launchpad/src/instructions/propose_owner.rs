@@ -0,0 +1,37 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+
+    // Ensure that the new owner is proposed by the auction owner
+    if ctx.accounts.owner.key() != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // The owner only changes once the new owner accepts
+    auction.pending_owner = new_owner;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Buyer, Config},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Register<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        init_if_needed,
+        space = 8 + std::mem::size_of::<Buyer>(),
+        payer = buyer,
+        seeds = [b"buyer", buyer.key().as_ref(), auction.key().as_ref()],
+        bump,
+    )]
+    pub buyer_pda: Box<Account<'info, Buyer>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Register>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+    let buyer_pda = &mut ctx.accounts.buyer_pda;
+    let current_ts = ctx.accounts.clock.unix_timestamp;
+
+    // Ensure that the buyer registers within the registration window,
+    // open auctions accept registrations until the auction ends
+    let is_open = if auction.registration_required {
+        current_ts >= auction.registration_start && current_ts < auction.registration_end
+    } else {
+        current_ts < auction.end_time
+    };
+    if !is_open {
+        return Err(LaunchpadError::RegistrationClosed.into());
+    }
+
+    if buyer_pda.registered_at == 0 {
+        buyer_pda.registered_at = current_ts;
+        buyer_pda.wallet = ctx.accounts.buyer.key();
+        buyer_pda.auction = auction.key();
+        auction.registered_count = auction
+            .registered_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Referral},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    #[account(
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        init,
+        space = 8 + std::mem::size_of::<Referral>(),
+        payer = referrer,
+        seeds = [b"referral", auction.key().as_ref(), referrer.key().as_ref(), mint.as_ref()],
+        bump,
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Open a referral for purchases paid in `mint`, the default pubkey for SOL
+pub fn handler(ctx: Context<RegisterReferrer>, mint: Pubkey) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    // Ensure that the auction rewards referrals
+    if ctx.accounts.auction.referral_bps == 0 {
+        return Err(LaunchpadError::InvalidReferral.into());
+    }
+
+    let referral = &mut ctx.accounts.referral;
+    referral.auction = ctx.accounts.auction.key();
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.mint = mint;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, Pool},
+    token_interface::{is_token_account, is_token_program, load_mint, transfer_checked},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.auction.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(mut, address = pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = holder_lp_token_account.owner == holder.key(),
+        constraint = holder_lp_token_account.mint == lp_mint.key()
+    )]
+    pub holder_lp_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = is_token_account(&pool_token_account, &pool.key(), &pool.token_mint)
+    )]
+    /// CHECK: checked to be a pool token account owned by pool
+    pub pool_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&holder_token_account, &holder.key(), &pool.token_mint)
+    )]
+    /// CHECK: checked to be a pool token account owned by holder
+    pub holder_token_account: AccountInfo<'info>,
+    #[account(address = pool.token_mint, owner = auction_token_program.key())]
+    /// CHECK: address is the pool's token mint
+    pub token_mint: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub token_program: Program<'info, Token>,
+    #[account(constraint = is_token_program(auction_token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program of the pool's token
+    pub auction_token_program: AccountInfo<'info>,
+}
+
+/// Burn `lp_amount` LP tokens for their share of both reserves
+pub fn handler(ctx: Context<RemoveLiquidity>, lp_amount: u64) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let pool = &mut ctx.accounts.pool;
+
+    // Ensure that the holder burns LP tokens they have
+    if lp_amount == 0 || lp_amount > ctx.accounts.holder_lp_token_account.amount {
+        return Err(LaunchpadError::InvalidTokenAmount.into());
+    }
+
+    let sol_amount = pool.share(pool.sol_reserve, lp_amount)?;
+    let token_amount = pool.share(pool.token_reserve, lp_amount)?;
+
+    let burn_lp = Burn {
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        from: ctx.accounts.holder_lp_token_account.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_lp);
+    burn(cpi_ctx, lp_amount)?;
+
+    let auction_key = pool.auction;
+    let pool_seed: &[&[&[_]]] = &[&["pool".as_bytes(), auction_key.as_ref(), &[pool.bump]]];
+
+    if token_amount > 0 {
+        let token_mint = &ctx.accounts.token_mint;
+        transfer_checked(
+            &ctx.accounts.auction_token_program,
+            &ctx.accounts.pool_token_account,
+            token_mint,
+            &ctx.accounts.holder_token_account,
+            &pool.to_account_info(),
+            pool_seed,
+            token_amount,
+            load_mint(token_mint)?.decimals,
+        )?;
+    }
+
+    let pool_info = pool.to_account_info();
+    let holder_info = ctx.accounts.holder.to_account_info();
+    **pool_info.try_borrow_mut_lamports()? = pool_info
+        .lamports()
+        .checked_sub(sol_amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **holder_info.try_borrow_mut_lamports()? = holder_info
+        .lamports()
+        .checked_add(sol_amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    pool.sol_reserve -= sol_amount;
+    pool.token_reserve -= token_amount;
+    pool.lp_supply -= lp_amount;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, Escrow},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RequestMilestone<'info> {
+    pub beneficiary: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.auction.as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Submit the current milestone for approval, opening the buyer vote if there is no approver
+pub fn handler(ctx: Context<RequestMilestone>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    // Ensure that the milestone is requested by the beneficiary
+    if ctx.accounts.beneficiary.key() != escrow.beneficiary {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that there is a funded milestone that isn't already requested
+    if !escrow.funded || escrow.finished() || escrow.vote_end != 0 {
+        return Err(LaunchpadError::InvalidMilestone.into());
+    }
+
+    escrow.vote_end = ctx
+        .accounts
+        .clock
+        .unix_timestamp
+        .saturating_add(escrow.vote_period);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Escrow, PAUSE_WITHDRAWALS},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ResolveMilestoneVote<'info> {
+    #[account(mut, address = escrow.auction)]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.auction.as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        seeds = [b"auction_vault", escrow.auction.as_ref()],
+        bump = escrow.vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub auction_vault: AccountInfo<'info>,
+    #[account(mut, address = escrow.beneficiary)]
+    /// CHECK: address is the escrow's beneficiary
+    pub beneficiary: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, address = config.fee_treasury)]
+    /// CHECK: address is the configured fee treasury
+    pub fee_treasury: AccountInfo<'info>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Close the buyer vote on the requested milestone, which passes unless more
+/// tickets voted against it than for it
+pub fn handler(ctx: Context<ResolveMilestoneVote>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    // Ensure that there is a buyer vote on a milestone
+    if escrow.approver != Pubkey::default() || escrow.vote_end == 0 || escrow.finished() {
+        return Err(LaunchpadError::VoteNotOpen.into());
+    }
+
+    // Ensure that the vote is over
+    if ctx.accounts.clock.unix_timestamp < escrow.vote_end {
+        return Err(LaunchpadError::VoteNotEnded.into());
+    }
+
+    if escrow.votes_against > escrow.votes_for {
+        escrow.rejected = true;
+    } else {
+        escrow.release(
+            &ctx.accounts.auction,
+            &ctx.accounts.auction_vault,
+            &ctx.accounts.beneficiary,
+            &ctx.accounts.fee_treasury,
+        )?;
+        ctx.accounts.auction.escrow_mode = !escrow.finished();
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Delegate, DelegateRole},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeRoleParams {
+    pub delegate: Pubkey,
+    pub role: DelegateRole,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RevokeRoleParams)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"delegate", auction.key().as_ref(), params.delegate.as_ref(), &[params.role as u8]],
+        bump
+    )]
+    pub delegate_pda: Box<Account<'info, Delegate>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>, _params: RevokeRoleParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    // Ensure that roles are revoked by the auction owner
+    if ctx.accounts.owner.key() != ctx.accounts.auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+    Ok(())
+}
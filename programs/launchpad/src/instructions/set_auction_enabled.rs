@@ -0,0 +1,45 @@
+use crate::{
+    error::LaunchpadError,
+    state::{load_delegate, Auction, Config, DelegateRole},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAuctionEnabled<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        seeds = [b"delegate", auction.key().as_ref(), authority.key().as_ref(), &[DelegateRole::Pauser as u8]],
+        bump
+    )]
+    /// CHECK: seeds has been checked, only loaded when the authority isn't the owner
+    pub delegate_pda: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<SetAuctionEnabled>, enabled: bool) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &mut ctx.accounts.auction;
+    let authority = ctx.accounts.authority.key();
+
+    // Ensure that the auction is paused by its owner or a pauser
+    if authority != auction.owner
+        && load_delegate(&ctx.accounts.delegate_pda, auction.key(), auction.owner, authority, DelegateRole::Pauser).is_none()
+    {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    auction.enabled = enabled;
+    Ok(())
+}
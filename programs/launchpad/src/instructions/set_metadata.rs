@@ -0,0 +1,89 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, AuctionMetadata, Config, MAX_SOCIAL_LINKS, MAX_URI_LEN},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MetadataParams {
+    pub description_uri: String,
+    pub logo_uri: String,
+    pub website: String,
+    pub social_links: Vec<String>,  // at most MAX_SOCIAL_LINKS
+    pub content_hash: [u8; 32],
+}
+
+impl MetadataParams {
+    /// Size of the metadata account holding these params, without the discriminator
+    pub fn space(&self) -> usize {
+        AuctionMetadata::BASE_SPACE
+            + self.description_uri.len()
+            + self.logo_uri.len()
+            + self.website.len()
+            + self.social_links.iter().map(|link| 4 + link.len()).sum::<usize>()
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: MetadataParams)]
+pub struct SetMetadata<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mut,
+        seeds = [b"metadata", auction.key().as_ref()],
+        bump = metadata.bump,
+        realloc = 8 + params.space(),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub metadata: Box<Account<'info, AuctionMetadata>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the auction's metadata, resizing its account to fit
+pub fn handler(ctx: Context<SetMetadata>, params: MetadataParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &ctx.accounts.auction;
+
+    // Ensure that the metadata is set by the auction owner
+    if ctx.accounts.owner.key() != auction.owner {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that buyers see the same metadata for the whole sale
+    if ctx.accounts.clock.unix_timestamp >= auction.sale_start_time() {
+        return Err(LaunchpadError::AuctionAlreadyStarted.into());
+    }
+
+    // Ensure that every field fits its bound
+    if params.social_links.len() > MAX_SOCIAL_LINKS
+        || [&params.description_uri, &params.logo_uri, &params.website]
+            .into_iter()
+            .chain(params.social_links.iter())
+            .any(|field| field.len() > MAX_URI_LEN)
+    {
+        return Err(LaunchpadError::InvalidMetadata.into());
+    }
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.description_uri = params.description_uri;
+    metadata.logo_uri = params.logo_uri;
+    metadata.website = params.website;
+    metadata.social_links = params.social_links;
+    metadata.content_hash = params.content_hash;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::MockPriceFeed;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MockPriceParams {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+}
+
+#[derive(Accounts)]
+pub struct SetMockPrice<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<MockPriceFeed>(),
+        seeds = [b"mock_price_feed", authority.key().as_ref()],
+        bump
+    )]
+    pub mock_price_feed: Box<Account<'info, MockPriceFeed>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Only available in builds with the `mock-oracle` feature, which tests use in place of
+/// a live oracle. Elsewhere it fails, so that no mock feed can be created.
+#[cfg(feature = "mock-oracle")]
+pub fn handler(ctx: Context<SetMockPrice>, params: MockPriceParams) -> Result<()> {
+    let mock_price_feed = &mut ctx.accounts.mock_price_feed;
+    mock_price_feed.authority = ctx.accounts.authority.key();
+    mock_price_feed.price = params.price;
+    mock_price_feed.conf = params.conf;
+    mock_price_feed.expo = params.expo;
+    mock_price_feed.publish_time = ctx.accounts.clock.unix_timestamp;
+    Ok(())
+}
+
+#[cfg(not(feature = "mock-oracle"))]
+pub fn handler(_ctx: Context<SetMockPrice>, _params: MockPriceParams) -> Result<()> {
+    Err(crate::error::LaunchpadError::MockOracleDisabled.into())
+}
@@ -0,0 +1,40 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, PAUSE_BUYS, PAUSE_WHITELIST, PAUSE_WITHDRAWALS},
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PauseParams {
+    pub paused: bool,     // pause every instruction
+    pub pause_flags: u8,  // or only the given PAUSE_* instruction classes
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<SetPause>, params: PauseParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Ensure that the pause is set by the protocol admin
+    if ctx.accounts.admin.key() != config.admin {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that only known instruction classes are paused
+    if params.pause_flags & !(PAUSE_BUYS | PAUSE_WITHDRAWALS | PAUSE_WHITELIST) != 0 {
+        return Err(LaunchpadError::InvalidPauseFlags.into());
+    }
+
+    config.paused = params.paused;
+    config.pause_flags = params.pause_flags;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, Config, Stake, StakeAllocation},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SnapshotStake<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    #[account(
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        seeds = [b"stake", staker.key().as_ref()],
+        bump,
+    )]
+    pub stake: Box<Account<'info, Stake>>,
+    #[account(
+        init_if_needed,
+        space = 8 + std::mem::size_of::<StakeAllocation>(),
+        payer = staker,
+        seeds = [b"stake_allocation", auction.key().as_ref(), staker.key().as_ref()],
+        bump,
+    )]
+    pub stake_allocation: Box<Account<'info, StakeAllocation>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SnapshotStake>) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let auction = &ctx.accounts.auction;
+    let stake = &ctx.accounts.stake;
+
+    // Ensure that the snapshot is taken before buying opens
+    if ctx.accounts.clock.unix_timestamp >= auction.sale_start_time() {
+        return Err(LaunchpadError::SnapshotClosed.into());
+    }
+
+    // Ensure that the stake can't be withdrawn before the auction ends
+    if stake.locked_until < auction.end_time {
+        return Err(LaunchpadError::StakeNotLocked.into());
+    }
+
+    let (tier, tickets) = ctx
+        .accounts
+        .config
+        .stake_tier(stake.amount)
+        .ok_or(LaunchpadError::InsufficientStake)?;
+
+    let allocation = &mut ctx.accounts.stake_allocation;
+    allocation.auction = auction.key();
+    allocation.staker = ctx.accounts.staker.key();
+    allocation.tier = tier;
+    allocation.tickets = tickets;
+    allocation.used = 0;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, Stake},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 8 + std::mem::size_of::<Stake>(),
+        payer = staker,
+        seeds = [b"stake", staker.key().as_ref()],
+        bump,
+    )]
+    pub stake: Box<Account<'info, Stake>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"stake_vault"],
+        bump = config.stake_vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub stake_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = staker_token_account.owner == staker.key(),
+        constraint = staker_token_account.mint == stake_mint.key()
+    )]
+    pub staker_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = staker,
+        associated_token::mint = stake_mint,
+        associated_token::authority = stake_vault,
+    )]
+    pub stake_vault_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(address = config.stake_mint)]
+    pub stake_mint: Box<Account<'info, Mint>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    // Ensure that something is staked
+    if amount == 0 {
+        return Err(LaunchpadError::InvalidTokenAmount.into());
+    }
+
+    let trans_spl = Transfer {
+        from: ctx.accounts.staker_token_account.to_account_info(),
+        to: ctx.accounts.stake_vault_token_account.to_account_info(),
+        authority: ctx.accounts.staker.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), trans_spl);
+    transfer(cpi_ctx, amount)?;
+
+    let config = &mut ctx.accounts.config;
+    config.total_staked = config
+        .total_staked
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Every stake restarts the lock period of the whole balance
+    let stake = &mut ctx.accounts.stake;
+    stake.staker = ctx.accounts.staker.key();
+    stake.amount = stake
+        .amount
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    stake.locked_until = ctx
+        .accounts
+        .clock
+        .unix_timestamp
+        .saturating_add(config.stake_lock_period);
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, Pool},
+    token_interface::{is_token_account, is_token_program, load_mint, transfer_checked},
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer as transfer_sol, Transfer as Transfer_Sol};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapParams {
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub sol_in: bool,  // SOL for tokens, or tokens for SOL
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", pool.auction.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(
+        mut,
+        constraint = is_token_account(&pool_token_account, &pool.key(), &pool.token_mint)
+    )]
+    /// CHECK: checked to be a pool token account owned by pool
+    pub pool_token_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = is_token_account(&user_token_account, &user.key(), &pool.token_mint)
+    )]
+    /// CHECK: checked to be a pool token account owned by user
+    pub user_token_account: AccountInfo<'info>,
+    #[account(address = pool.token_mint, owner = token_program.key())]
+    /// CHECK: address is the pool's token mint
+    pub token_mint: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub system_program: Program<'info, System>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let pool = &mut ctx.accounts.pool;
+
+    // Ensure that the pool has been seeded
+    if pool.sol_reserve == 0 || pool.token_reserve == 0 || params.amount_in == 0 {
+        return Err(LaunchpadError::InsufficientLiquidity.into());
+    }
+
+    // Tokens sent to the pool arrive less any transfer fee
+    let mint = load_mint(&ctx.accounts.token_mint)?;
+    let (reserve_in, reserve_out, amount_in) = if params.sol_in {
+        (pool.sol_reserve, pool.token_reserve, params.amount_in)
+    } else {
+        (pool.token_reserve, pool.sol_reserve, mint.received(params.amount_in)?)
+    };
+    let amount_out = Pool::swap_output(amount_in, reserve_in, reserve_out)?;
+
+    // Ensure that the trade isn't worse than the user accepts, nor drains the pool
+    if amount_out < params.min_amount_out {
+        return Err(LaunchpadError::SlippageExceeded.into());
+    }
+    if amount_out == 0 || amount_out >= reserve_out {
+        return Err(LaunchpadError::InsufficientLiquidity.into());
+    }
+
+    let auction_key = pool.auction;
+    let pool_seed: &[&[&[_]]] = &[&["pool".as_bytes(), auction_key.as_ref(), &[pool.bump]]];
+    let pool_info = pool.to_account_info();
+    let user_info = ctx.accounts.user.to_account_info();
+
+    if params.sol_in {
+        let trans_sol = Transfer_Sol {
+            from: user_info,
+            to: pool_info,
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), trans_sol);
+        transfer_sol(cpi_ctx, params.amount_in)?;
+
+        transfer_checked(
+            &ctx.accounts.token_program,
+            &ctx.accounts.pool_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.user_token_account,
+            &pool.to_account_info(),
+            pool_seed,
+            amount_out,
+            mint.decimals,
+        )?;
+
+        pool.sol_reserve += params.amount_in;
+        pool.token_reserve -= amount_out;
+    } else {
+        transfer_checked(
+            &ctx.accounts.token_program,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.pool_token_account,
+            &user_info,
+            &[],
+            params.amount_in,
+            mint.decimals,
+        )?;
+
+        **pool_info.try_borrow_mut_lamports()? = pool_info
+            .lamports()
+            .checked_sub(amount_out)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **user_info.try_borrow_mut_lamports()? = user_info
+            .lamports()
+            .checked_add(amount_out)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        pool.token_reserve += amount_in;
+        pool.sol_reserve -= amount_out;
+    }
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, Stake},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct UnstakeTokens<'info> {
+    pub staker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"stake", staker.key().as_ref()],
+        bump,
+    )]
+    pub stake: Box<Account<'info, Stake>>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"stake_vault"],
+        bump = config.stake_vault_bump,
+    )]
+    /// CHECK: seeds has been checked
+    pub stake_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = staker_token_account.owner == staker.key(),
+        constraint = staker_token_account.mint == stake_mint.key()
+    )]
+    pub staker_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = stake_vault_token_account.owner == stake_vault.key(),
+        constraint = stake_vault_token_account.mint == stake_mint.key()
+    )]
+    pub stake_vault_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(address = config.stake_mint)]
+    pub stake_mint: Box<Account<'info, Mint>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let stake = &mut ctx.accounts.stake;
+
+    // Ensure that the lock period is over
+    if ctx.accounts.clock.unix_timestamp < stake.locked_until {
+        return Err(LaunchpadError::StakeLocked.into());
+    }
+
+    // Ensure that the staker doesn't withdraw more than staked
+    if amount == 0 || amount > stake.amount {
+        return Err(LaunchpadError::InsufficientStake.into());
+    }
+
+    // Generate stake vault seed
+    let stake_vault_seed: &[&[&[_]]] = &[&["stake_vault".as_bytes(), &[ctx.accounts.config.stake_vault_bump]]];
+
+    let trans_spl = Transfer {
+        from: ctx.accounts.stake_vault_token_account.to_account_info(),
+        to: ctx.accounts.staker_token_account.to_account_info(),
+        authority: ctx.accounts.stake_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        trans_spl,
+        stake_vault_seed,
+    );
+    transfer(cpi_ctx, amount)?;
+
+    stake.amount -= amount;
+    ctx.accounts.config.total_staked -= amount;
+    Ok(())
+}
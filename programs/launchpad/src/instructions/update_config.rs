@@ -0,0 +1,37 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Config, MAX_FEE_BPS},
+};
+use anchor_lang::prelude::*;
+
+use super::ConfigParams;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Ensure that the config is updated by the protocol admin
+    if ctx.accounts.admin.key() != config.admin {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    // Ensure that the fee is at most 100%
+    if params.fee_bps > MAX_FEE_BPS {
+        return Err(LaunchpadError::InvalidFee.into());
+    }
+
+    config.fee_bps = params.fee_bps;
+    config.fee_treasury = params.fee_treasury;
+    config.listing_fee = params.listing_fee;
+    Ok(())
+}
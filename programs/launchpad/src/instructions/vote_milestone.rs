@@ -0,0 +1,76 @@
+use crate::{
+    error::LaunchpadError,
+    state::{Buyer, Config, Escrow, MilestoneVote},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct VoteMilestone<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.auction.as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        seeds = [b"buyer", buyer.key().as_ref(), escrow.auction.as_ref()],
+        bump,
+    )]
+    pub buyer_pda: Box<Account<'info, Buyer>>,
+    #[account(
+        init,
+        space = 8 + std::mem::size_of::<MilestoneVote>(),
+        payer = buyer,
+        seeds = [
+            b"milestone_vote",
+            escrow.auction.as_ref(),
+            buyer.key().as_ref(),
+            &[escrow.current_milestone],
+        ],
+        bump,
+    )]
+    pub milestone_vote: Box<Account<'info, MilestoneVote>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Vote on the requested milestone with the weight of the tickets held
+pub fn handler(ctx: Context<VoteMilestone>, approve: bool) -> Result<()> {
+    // Ensure that the program is not paused
+    ctx.accounts.config.check_active(0)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    let weight = ctx.accounts.buyer_pda.tickets as u64;
+
+    // Ensure that milestones are voted on and the vote is open
+    if escrow.approver != Pubkey::default()
+        || escrow.vote_end == 0
+        || escrow.finished()
+        || ctx.accounts.clock.unix_timestamp >= escrow.vote_end
+    {
+        return Err(LaunchpadError::VoteNotOpen.into());
+    }
+
+    // Ensure that the voter holds tickets
+    if weight == 0 {
+        return Err(LaunchpadError::Unauthorized.into());
+    }
+
+    if approve {
+        escrow.votes_for += weight;
+    } else {
+        escrow.votes_against += weight;
+    }
+
+    let milestone_vote = &mut ctx.accounts.milestone_vote;
+    milestone_vote.approve = approve;
+    milestone_vote.weight = weight;
+    Ok(())
+}
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::{state::{whitelist::Whitelist, Auction}, error::LaunchpadError};
+use crate::{
+    state::{whitelist::Whitelist, load_delegate, Auction, Config, DelegateRole, MAX_TIERS, PAUSE_WHITELIST},
+    error::LaunchpadError,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct WhitelistParams {
     pub whitelisted: bool,
+    pub tier: u8,
 }
 
 #[derive(Accounts)]
@@ -20,10 +24,21 @@ pub struct WhitelistUser<'info> {
     pub whitelist_pda: Box<Account<'info, Whitelist>>,
     #[account(
         mut,
-        seeds = [b"auction",auction.name.as_bytes()],
-        bump
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
     )]
     pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"delegate", auction.key().as_ref(), creator.key().as_ref(), &[DelegateRole::WhitelistManager as u8]],
+        bump
+    )]
+    /// CHECK: seeds has been checked, only loaded when the creator isn't the owner
+    pub delegate_pda: AccountInfo<'info>,
     /// CHECK:
     pub whitelist_user: AccountInfo<'info>,
     pub rent: Sysvar<'info, Rent>,
@@ -31,11 +46,25 @@ pub struct WhitelistUser<'info> {
 }
 
 pub fn handler(ctx: Context<WhitelistUser>, params: WhitelistParams) -> Result<()> {
-    // Ensure that the creator is the owner of the auction
-    if ctx.accounts.creator.key != &ctx.accounts.auction.owner {
+    // Ensure that whitelisting is not paused
+    ctx.accounts.config.check_active(PAUSE_WHITELIST)?;
+
+    // Ensure that the creator is the owner of the auction or a whitelist manager
+    let creator = ctx.accounts.creator.key();
+    let auction = &ctx.accounts.auction;
+    if creator != auction.owner
+        && load_delegate(&ctx.accounts.delegate_pda, auction.key(), auction.owner, creator, DelegateRole::WhitelistManager).is_none()
+    {
         return Err(LaunchpadError::InvalidAuction.into());
     }
+
+    // Ensure that the tier has a purchase delay
+    if params.tier as usize >= MAX_TIERS {
+        return Err(LaunchpadError::InvalidTier.into());
+    }
+
     let whitelist = &mut ctx.accounts.whitelist_pda;
     whitelist.whitelisted = params.whitelisted;
+    whitelist.tier = params.tier;
     Ok(())
 }
\ No newline at end of file
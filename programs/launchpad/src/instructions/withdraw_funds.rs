@@ -1,9 +1,10 @@
-use crate::{error::LaunchpadError, state::Auction};
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
-use anchor_spl::token::{
-    transfer as transfer_spl, Mint, Token, TokenAccount, Transfer as Transfer_Spl,
+use crate::{
+    error::LaunchpadError,
+    settlement::Settlement,
+    state::{load_delegate, Auction, Config, DelegateRole, UnsoldTokenPolicy, PAUSE_WITHDRAWALS},
+    token_interface::{is_token_account, is_token_program},
 };
+use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct WithdrawFunds<'info> {
@@ -11,43 +12,97 @@ pub struct WithdrawFunds<'info> {
     pub creator: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"auction", auction.name.as_bytes()],
-        bump
+        seeds = [b"auction", auction.pda_seed().as_ref()],
+        bump = auction.bump
     )]
     pub auction: Box<Account<'info, Auction>>,
     #[account(
         mut,
         seeds = [b"auction_vault", auction.key().as_ref()],
-        bump,
+        bump = auction.vault_bump,
     )]
     /// CHECK: seeds has been checked
     pub auction_vault: AccountInfo<'info>,
+    #[account(
+        seeds = [b"delegate", auction.key().as_ref(), creator.key().as_ref(), &[DelegateRole::Withdrawer as u8]],
+        bump
+    )]
+    /// CHECK: seeds has been checked, only loaded when the creator isn't the owner
+    pub delegate_pda: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: checked against the owner or the withdrawer's fixed destination
+    pub destination: AccountInfo<'info>,
     #[account(
         mut,
-        constraint = auction_vault_token_account.owner == auction_vault.key(),
-        constraint = auction_vault_token_account.mint == auction_token.key()
+        constraint = is_token_account(&auction_vault_token_account, &auction_vault.key(), &auction_token.key())
     )]
-    pub auction_vault_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: checked to be an auction_token account owned by auction_vault
+    pub auction_vault_token_account: AccountInfo<'info>,
     #[account(
         mut,
-        constraint = creator_auction_token_account.owner == creator.key(),
-        constraint = creator_auction_token_account.mint == auction_token.key()
+        constraint = auction.unsold_token_policy == UnsoldTokenPolicy::Burn
+            || is_token_account(&creator_auction_token_account, &auction.unsold_recipient(destination.key()), &auction_token.key())
+    )]
+    /// CHECK: checked to be an auction_token account owned by destination, or by the
+    /// unsold destination with TransferTo, unused when the unsold tokens are burnt
+    pub creator_auction_token_account: AccountInfo<'info>,
+    #[account(mut, owner = token_program.key())]
+    /// CHECK: owned by the token program, loaded as a mint, burnt from with Burn
+    pub auction_token: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, address = config.fee_treasury)]
+    /// CHECK: address is the configured fee treasury
+    pub fee_treasury: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", auction.key().as_ref()],
+        bump,
     )]
-    pub creator_auction_token_account: Box<Account<'info, TokenAccount>>,
-    pub auction_token: Box<Account<'info, Mint>>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: seeds has been checked, only loaded in escrow mode
+    pub escrow: AccountInfo<'info>,
+    #[account(constraint = is_token_program(token_program.key))]
+    /// CHECK: the SPL Token or the Token-2022 program
+    pub token_program: AccountInfo<'info>,
     pub clock: Sysvar<'info, Clock>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<WithdrawFunds>) -> Result<()> {
+/// Bid token proceeds are withdrawn through `remaining_accounts`, passed as
+/// `[payment_option, auction_vault_bid_token_account, destination_bid_token_account,
+/// treasury_bid_token_account, bid_token, bid_token_program]` for every accepted currency,
+/// each one exactly once.
+///
+/// Funds go to the auction owner, or to the fixed destination of a withdrawer delegate,
+/// and are settled the same way as by `finalize_auction`.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFunds<'info>>) -> Result<()> {
+    // Ensure that withdrawals are not paused
+    ctx.accounts.config.check_active(PAUSE_WITHDRAWALS)?;
+
     let auction = &mut ctx.accounts.auction;
-    let auction_vault: &AccountInfo<'_> = &ctx.accounts.auction_vault;
     let creator = &ctx.accounts.creator;
+    let destination = &ctx.accounts.destination;
+
+    // Ensure that the auction has not been finalized already
+    if auction.finalized_at != 0 {
+        return Err(LaunchpadError::AuctionFinalized.into());
+    }
+
+    // Ensure that the withdrawal is done by the auction creator or a withdrawer
+    let destination_key = if *creator.key == auction.owner {
+        auction.owner
+    } else {
+        load_delegate(&ctx.accounts.delegate_pda, auction.key(), auction.owner, creator.key(), DelegateRole::Withdrawer)
+            .ok_or(LaunchpadError::Unauthorized)?
+            .destination
+    };
 
-    // Ensure that the withdrawal is done by the auction creator
-    if *creator.key != auction.owner {
-        return Err(LaunchpadError::Unauthorized.into());
+    // Ensure that the funds go to the owner or the withdrawer's fixed destination
+    if destination.key() != destination_key {
+        return Err(LaunchpadError::InvalidDestination.into());
     }
 
     // Ensure that the auction has ended
@@ -60,69 +115,23 @@ pub fn handler(ctx: Context<WithdrawFunds>) -> Result<()> {
         return Err(LaunchpadError::AuctionDisabled.into());
     }
 
-    // Generate auction seed
-    let auction_key = auction.key();
-
-    let (_, bump_seed) = Pubkey::find_program_address(
-        &["auction_vault".as_bytes(), auction_key.as_ref()],
-        ctx.program_id,
-    );
-
-    let auction_vault_seed: &[&[&[_]]] = &[&[
-        "auction_vault".as_bytes(),
-        auction_key.as_ref(),
-        &[bump_seed],
-    ]];
-
-    // remaining tokens
-    let remaining_tokens_in_auction_pool = auction.remaining_tokens * LAMPORTS_PER_SOL;
-
-    // Transfer if there are any remaining tokens
-    if remaining_tokens_in_auction_pool > 0 {
-        let trans_spl = Transfer_Spl {
-            from: ctx.accounts.auction_vault_token_account.to_account_info(),
-            to: ctx.accounts.creator_auction_token_account.to_account_info(),
-            authority: auction_vault.to_account_info(),
-        };
-        let ctx: CpiContext<'_, '_, '_, '_, _> = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            trans_spl,
-            auction_vault_seed,
-        );
-        transfer_spl(ctx, remaining_tokens_in_auction_pool)?;
-    }
-
-    // Transfer sol if tokens have been sold
-    // ticket_price (in SOL) calc: funding_demand / no.of tickets
-    let ticket_price = (auction.funding_demand * LAMPORTS_PER_SOL) / (auction.tokens_in_pool/auction.token_quantity_per_ticket);
-    // unit_token_price (in SOL) i.e. 1 auction token is worth how much SOL
-    let unit_token_price = ticket_price / auction.token_quantity_per_ticket;
-
-    if auction.tokens_in_pool != auction.remaining_tokens && auction.pay_with_native {
-        let sol_amount = (auction.tokens_in_pool - auction.remaining_tokens) * unit_token_price;
-
-        **auction_vault.try_borrow_mut_lamports()? = auction_vault
-            .lamports()
-            .checked_sub(sol_amount)
-            .ok_or(ProgramError::InvalidArgument)?;
- 
-        **creator.try_borrow_mut_lamports()? = creator
-            .lamports()
-            .checked_add(sol_amount)
-            .ok_or(ProgramError::InvalidArgument)?;
+    // Ensure that the promised liquidity is seeded before the proceeds are taken
+    if auction.liquidity_bps > 0 && !auction.liquidity_added {
+        return Err(LaunchpadError::LiquidityNotAdded.into());
     }
 
-    // Reset the auction state
-    auction.owner = Pubkey::default();
-    auction.enabled = false;
-    auction.fixed_amount = false;
-    auction.start_time = 0;
-    auction.end_time = 0;
-    auction.tokens_in_pool = 0;
-    auction.remaining_tokens = 0;
-    auction.pay_with_native = false;
-    auction.pre_sale = false;
-    auction.pre_sale_start_time = 0;
-    auction.pre_sale_end_time = 0;
-    Ok(())
+    let settlement = Settlement {
+        auction_vault: &ctx.accounts.auction_vault,
+        auction_vault_token_account: &ctx.accounts.auction_vault_token_account,
+        auction_token: &ctx.accounts.auction_token,
+        token_program: &ctx.accounts.token_program,
+        destination,
+        unsold_token_account: &ctx.accounts.creator_auction_token_account,
+        config: &ctx.accounts.config,
+        fee_treasury: &ctx.accounts.fee_treasury,
+        escrow: &ctx.accounts.escrow,
+        payment_accounts: ctx.remaining_accounts,
+    };
+    let auction_key = auction.key();
+    settlement.settle(auction, auction_key, ctx.accounts.clock.unix_timestamp)
 }
@@ -1,10 +1,18 @@
 //! Launchpad program entrypoint
 
-// #![allow(clippy::result_large_err)]
+#![allow(clippy::result_large_err)]
 use anchor_lang::prelude::*;
+mod attestation;
 mod error;
+mod events;
 mod instructions;
+mod oracle;
+mod payment;
+mod settlement;
 mod state;
+mod token_gate;
+mod token_interface;
+mod vault;
 
 use instructions::*;
 
@@ -15,35 +23,185 @@ mod launchpad {
 
     use super::*;
 
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        initialize_config::handler(ctx, params)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        update_config::handler(ctx, params)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, params: PauseParams) -> Result<()> {
+        set_pause::handler(ctx, params)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        propose_admin::handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::handler(ctx)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        migrate_config::handler(ctx)
+    }
+
     pub fn init_auction(ctx: Context<InitAuction>, params: InitAuctionParams) -> Result<()> {
         init_auction::handler(ctx, params)
     }
 
+    pub fn migrate_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAuction<'info>>,
+    ) -> Result<()> {
+        migrate_auction::handler(ctx)
+    }
+
+    pub fn set_metadata(ctx: Context<SetMetadata>, params: MetadataParams) -> Result<()> {
+        set_metadata::handler(ctx, params)
+    }
+
+    pub fn propose_owner(ctx: Context<ProposeOwner>, new_owner: Pubkey) -> Result<()> {
+        propose_owner::handler(ctx, new_owner)
+    }
+
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        accept_owner::handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, params: GrantRoleParams) -> Result<()> {
+        grant_role::handler(ctx, params)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, params: RevokeRoleParams) -> Result<()> {
+        revoke_role::handler(ctx, params)
+    }
+
+    pub fn set_auction_enabled(ctx: Context<SetAuctionEnabled>, enabled: bool) -> Result<()> {
+        set_auction_enabled::handler(ctx, enabled)
+    }
+
+    pub fn init_buyer_registry(ctx: Context<InitBuyerRegistry>) -> Result<()> {
+        init_buyer_registry::handler(ctx)
+    }
+
     pub fn add_token(ctx: Context<AddToken>) -> Result<()> {
         add_token::handler(ctx)
     }
 
-    pub fn buy_token_using_spl(ctx: Context<BuyTokensSpl>, spl_amount: u64) -> Result<()> {
-        buy_token_using_spl::handler(ctx, spl_amount)
+    pub fn register(ctx: Context<Register>) -> Result<()> {
+        register::handler(ctx)
+    }
+
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+        phase: SalePhase,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        buy::handler(ctx, phase, referrer)
     }
 
-    pub fn buy_token_using_sol(ctx: Context<BuyTokensSol>) -> Result<()> {
-        buy_token_using_sol::handler(ctx)
+    pub fn add_payment_option(
+        ctx: Context<AddPaymentOption>,
+        params: PaymentOptionParams,
+    ) -> Result<()> {
+        add_payment_option::handler(ctx, params)
     }
 
-    pub fn withdraw_funds(ctx: Context<WithdrawFunds>) -> Result<()> {
+    pub fn withdraw_funds<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFunds<'info>>,
+    ) -> Result<()> {
         withdraw_funds::handler(ctx)
     }
 
+    pub fn finalize_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeAuction<'info>>,
+    ) -> Result<()> {
+        finalize_auction::handler(ctx)
+    }
+
     pub fn whitelist(ctx: Context<WhitelistUser>, params: WhitelistParams) -> Result<()> {
         whitelist::handler(ctx, params)
     }
 
-    pub fn pre_sale_buy_using_spl(ctx: Context<PreSaleBuyUsingSpl>, spl_amount: u64) -> Result<()> {
-        pre_sale_buy_using_spl::handler(ctx, spl_amount)
+    pub fn configure_staking(ctx: Context<ConfigureStaking>, params: StakingParams) -> Result<()> {
+        configure_staking::handler(ctx, params)
+    }
+
+    pub fn stake(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
+        stake::handler(ctx, amount)
+    }
+
+    pub fn unstake(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
+        unstake::handler(ctx, amount)
+    }
+
+    pub fn snapshot_stake(ctx: Context<SnapshotStake>) -> Result<()> {
+        snapshot_stake::handler(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, mint: Pubkey) -> Result<()> {
+        register_referrer::handler(ctx, mint)
+    }
+
+    pub fn claim_referral_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimReferralReward<'info>>,
+    ) -> Result<()> {
+        claim_referral_reward::handler(ctx)
+    }
+
+    pub fn finalize_with_liquidity(ctx: Context<FinalizeWithLiquidity>) -> Result<()> {
+        finalize_with_liquidity::handler(ctx)
+    }
+
+    pub fn swap(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
+        swap::handler(ctx, params)
+    }
+
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, lp_amount: u64) -> Result<()> {
+        remove_liquidity::handler(ctx, lp_amount)
+    }
+
+    pub fn claim_lp_tokens(ctx: Context<ClaimLpTokens>) -> Result<()> {
+        claim_lp_tokens::handler(ctx)
+    }
+
+    pub fn lock_team_allocation(
+        ctx: Context<LockTeamAllocation>,
+        params: TeamAllocationParams,
+    ) -> Result<()> {
+        lock_team_allocation::handler(ctx, params)
+    }
+
+    pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
+        claim_vested_tokens::handler(ctx)
+    }
+
+    pub fn init_escrow(ctx: Context<InitEscrow>, params: EscrowParams) -> Result<()> {
+        init_escrow::handler(ctx, params)
+    }
+
+    pub fn request_milestone(ctx: Context<RequestMilestone>) -> Result<()> {
+        request_milestone::handler(ctx)
+    }
+
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, approve: bool) -> Result<()> {
+        approve_milestone::handler(ctx, approve)
+    }
+
+    pub fn vote_milestone(ctx: Context<VoteMilestone>, approve: bool) -> Result<()> {
+        vote_milestone::handler(ctx, approve)
+    }
+
+    pub fn resolve_milestone_vote(ctx: Context<ResolveMilestoneVote>) -> Result<()> {
+        resolve_milestone_vote::handler(ctx)
+    }
+
+    pub fn claim_escrow_refund(ctx: Context<ClaimEscrowRefund>) -> Result<()> {
+        claim_escrow_refund::handler(ctx)
     }
 
-    pub fn pre_sale_buy_using_sol(ctx: Context<PreSaleBuyUsingSol>) -> Result<()> {
-        pre_sale_buy_using_sol::handler(ctx)
+    pub fn set_mock_price(ctx: Context<SetMockPrice>, params: MockPriceParams) -> Result<()> {
+        set_mock_price::handler(ctx, params)
     }
 }
@@ -0,0 +1,145 @@
+//! Price feeds used to convert USD denominated ticket prices into SOL
+
+use crate::error::LaunchpadError;
+#[cfg(feature = "mock-oracle")]
+use crate::state::MockPriceFeed;
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+
+/// Pyth oracle program on mainnet-beta
+pub mod pyth_mainnet {
+    anchor_lang::declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+}
+
+/// Pyth oracle program on devnet
+pub mod pyth_devnet {
+    anchor_lang::declare_id!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+}
+
+/// 1 USD in the 6 decimal units used by `Auction.usd_ticket_price`
+const USD_UNIT: u128 = 1_000_000;
+
+/// A price quote of `price * 10^expo` USD per SOL
+#[derive(Clone, Copy, Debug)]
+pub struct FeedPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl FeedPrice {
+    /// Ensure that the price is positive, recent and precise enough to be used
+    pub fn validate(&self, now: i64, max_age: i64, max_confidence_bps: u16) -> Result<()> {
+        if self.price <= 0 {
+            return Err(LaunchpadError::InvalidPrice.into());
+        }
+
+        if now.saturating_sub(self.publish_time) > max_age {
+            return Err(LaunchpadError::StalePrice.into());
+        }
+
+        // conf / price > max_confidence_bps / 10_000
+        if (self.conf as u128) * 10_000 > (self.price as u128) * (max_confidence_bps as u128) {
+            return Err(LaunchpadError::PriceConfidenceTooWide.into());
+        }
+        Ok(())
+    }
+
+    /// Convert a USD amount (6 decimals) into lamports at this price
+    pub fn usd_to_lamports(&self, usd_amount: u64) -> Result<u64> {
+        let scale = 10u128
+            .checked_pow(self.expo.unsigned_abs())
+            .ok_or(LaunchpadError::InvalidPrice)?;
+        let numerator = (usd_amount as u128) * (LAMPORTS_PER_SOL as u128);
+        let (numerator, denominator) = if self.expo < 0 {
+            (numerator.checked_mul(scale), (self.price as u128).checked_mul(USD_UNIT))
+        } else {
+            (Some(numerator), (self.price as u128).checked_mul(scale).and_then(|p| p.checked_mul(USD_UNIT)))
+        };
+        let lamports = numerator
+            .zip(denominator)
+            .and_then(|(n, d)| n.checked_div(d))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        u64::try_from(lamports).map_err(|_| ProgramError::ArithmeticOverflow.into())
+    }
+}
+
+/// Layout of a price feed account that can quote SOL/USD
+pub trait PriceFeed: Sized {
+    fn load(feed: &AccountInfo) -> Result<Self>;
+    fn price(&self) -> FeedPrice;
+}
+
+#[cfg(feature = "mock-oracle")]
+impl PriceFeed for MockPriceFeed {
+    fn load(feed: &AccountInfo) -> Result<Self> {
+        let account: Account<MockPriceFeed> = Account::try_from(feed)?;
+        Ok(account.into_inner())
+    }
+
+    fn price(&self) -> FeedPrice {
+        FeedPrice {
+            price: self.price,
+            conf: self.conf,
+            expo: self.expo,
+            publish_time: self.publish_time,
+        }
+    }
+}
+
+/// Aggregate price of a Pyth (v2) price account
+pub struct PythPriceFeed(FeedPrice);
+
+impl PythPriceFeed {
+    const MAGIC: u32 = 0xa1b2c3d4;
+    const PRICE_ACCOUNT_TYPE: u32 = 3;
+    const TRADING_STATUS: u32 = 1;
+    const LEN: usize = 240;
+}
+
+impl PriceFeed for PythPriceFeed {
+    fn load(feed: &AccountInfo) -> Result<Self> {
+        let data = feed.try_borrow_data()?;
+        if data.len() < Self::LEN {
+            return Err(LaunchpadError::InvalidPriceFeed.into());
+        }
+
+        let u32_at = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        let i64_at = |offset: usize| i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        if u32_at(0) != Self::MAGIC || u32_at(8) != Self::PRICE_ACCOUNT_TYPE {
+            return Err(LaunchpadError::InvalidPriceFeed.into());
+        }
+
+        // Only an aggregate that is currently trading is a usable price
+        if u32_at(224) != Self::TRADING_STATUS {
+            return Err(LaunchpadError::InvalidPrice.into());
+        }
+
+        Ok(PythPriceFeed(FeedPrice {
+            price: i64_at(208),
+            conf: i64_at(216) as u64,
+            expo: u32_at(20) as i32,
+            publish_time: i64_at(96),
+        }))
+    }
+
+    fn price(&self) -> FeedPrice {
+        self.0
+    }
+}
+
+/// Load the SOL/USD price from a feed account, picking the layout by its owner.
+/// Mock feeds are only accepted by builds with the `mock-oracle` feature.
+pub fn load_price(feed: &AccountInfo) -> Result<FeedPrice> {
+    #[cfg(feature = "mock-oracle")]
+    if feed.owner == &crate::ID {
+        return Ok(MockPriceFeed::load(feed)?.price());
+    }
+
+    if feed.owner == &pyth_mainnet::ID || feed.owner == &pyth_devnet::ID {
+        Ok(PythPriceFeed::load(feed)?.price())
+    } else {
+        Err(LaunchpadError::InvalidPriceFeed.into())
+    }
+}
@@ -0,0 +1,209 @@
+//! Assets buyers pay for tickets in: native SOL or an accepted SPL token
+
+use crate::{
+    error::LaunchpadError,
+    oracle::load_price,
+    state::{Auction, Buyer, PaymentOption},
+    token_interface::{is_token_account, is_token_program, load_mint, transfer_checked, MintData},
+};
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_lang::system_program::{transfer as transfer_sol, Transfer as Transfer_Sol};
+
+/// A currency accepted for tickets, so that every purchase goes through the same validations
+pub trait PaymentAsset<'info> {
+    /// Mint that referral rewards are tracked in, the default key for SOL
+    fn mint(&self) -> Pubkey;
+
+    /// Ensure that the auction accepts payments in this asset
+    fn check_accepted(&self, auction: &Auction) -> Result<()>;
+
+    /// Price of one ticket in base units of this asset
+    fn ticket_price(&self, auction: &Auction, clock: &Clock) -> Result<u64>;
+
+    /// Move `amount` from the buyer to the auction vault and return the amount the vault received
+    fn collect(&self, buyer: &AccountInfo<'info>, amount: u64) -> Result<u64>;
+
+    /// Record `received` as proceeds to withdraw, less the `referral_reward` kept for the referrer
+    fn record(
+        &mut self,
+        auction: &mut Auction,
+        buyer_pda: &mut Buyer,
+        paid: u64,
+        received: u64,
+        referral_reward: u64,
+    ) -> Result<()>;
+}
+
+/// Lamports paid straight into the auction vault
+pub struct NativeSol<'info> {
+    pub auction_vault: AccountInfo<'info>,
+    pub price_feed: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> PaymentAsset<'info> for NativeSol<'info> {
+    fn mint(&self) -> Pubkey {
+        Pubkey::default()
+    }
+
+    fn check_accepted(&self, auction: &Auction) -> Result<()> {
+        if !auction.pay_with_native {
+            return Err(LaunchpadError::NonNativeAuction.into());
+        }
+        Ok(())
+    }
+
+    /// funding_demand / no. of tickets, or for usd denominated auctions
+    /// usd_ticket_price at the current SOL/USD price
+    fn ticket_price(&self, auction: &Auction, clock: &Clock) -> Result<u64> {
+        if auction.usd_denominated {
+            let price = load_price(&self.price_feed)?;
+            price.validate(
+                clock.unix_timestamp,
+                auction.max_price_age,
+                auction.max_price_confidence_bps,
+            )?;
+            price.usd_to_lamports(auction.usd_ticket_price)
+        } else {
+            Ok((auction.funding_demand * LAMPORTS_PER_SOL)
+                / (auction.tokens_in_pool / auction.token_quantity_per_ticket))
+        }
+    }
+
+    fn collect(&self, buyer: &AccountInfo<'info>, amount: u64) -> Result<u64> {
+        let trans_sol = Transfer_Sol {
+            from: buyer.clone(),
+            to: self.auction_vault.clone(),
+        };
+        transfer_sol(CpiContext::new(self.system_program.clone(), trans_sol), amount)?;
+        Ok(amount)
+    }
+
+    fn record(
+        &mut self,
+        auction: &mut Auction,
+        buyer_pda: &mut Buyer,
+        paid: u64,
+        received: u64,
+        referral_reward: u64,
+    ) -> Result<()> {
+        auction.sol_referral_reserved = auction
+            .sol_referral_reserved
+            .checked_add(referral_reward)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        auction.sol_collected = auction
+            .sol_collected
+            .checked_add(received - referral_reward)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Escrow refunds are paid pro rata to what each buyer paid
+        buyer_pda.sol_paid = buyer_pda
+            .sol_paid
+            .checked_add(paid)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        auction.sol_paid_total = auction
+            .sol_paid_total
+            .checked_add(paid)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Bid tokens of one of the auction's payment options, of either token program
+pub struct SplToken<'info> {
+    payment_option: Account<'info, PaymentOption>,
+    bid_token: AccountInfo<'info>,
+    bid_mint: MintData,
+    bid_token_program: AccountInfo<'info>,
+    buyer_bid_token_account: AccountInfo<'info>,
+    auction_vault_bid_token_account: AccountInfo<'info>,
+}
+
+impl<'info> SplToken<'info> {
+    /// Load the payment option and ensure that the bid token accounts belong to it
+    #[allow(clippy::too_many_arguments)]
+    pub fn load(
+        auction_key: Pubkey,
+        buyer: Pubkey,
+        auction_vault: Pubkey,
+        payment_option: &AccountInfo<'info>,
+        bid_token: &AccountInfo<'info>,
+        bid_token_program: &AccountInfo<'info>,
+        buyer_bid_token_account: &AccountInfo<'info>,
+        auction_vault_bid_token_account: &AccountInfo<'info>,
+    ) -> Result<Self> {
+        let payment_option: Account<PaymentOption> = Account::try_from(payment_option)?;
+        if payment_option.auction != auction_key
+            || payment_option.mint != bid_token.key()
+            || bid_token.owner != bid_token_program.key
+            || !is_token_program(bid_token_program.key)
+            || !is_token_account(buyer_bid_token_account, &buyer, bid_token.key)
+            || !is_token_account(auction_vault_bid_token_account, &auction_vault, bid_token.key)
+        {
+            return Err(LaunchpadError::InvalidPaymentAccounts.into());
+        }
+
+        Ok(Self {
+            payment_option,
+            bid_mint: load_mint(bid_token)?,
+            bid_token: bid_token.clone(),
+            bid_token_program: bid_token_program.clone(),
+            buyer_bid_token_account: buyer_bid_token_account.clone(),
+            auction_vault_bid_token_account: auction_vault_bid_token_account.clone(),
+        })
+    }
+}
+
+impl<'info> PaymentAsset<'info> for SplToken<'info> {
+    fn mint(&self) -> Pubkey {
+        self.payment_option.mint
+    }
+
+    /// Escrowed auctions are only paid in SOL, which buyers get refunds in
+    fn check_accepted(&self, auction: &Auction) -> Result<()> {
+        if auction.escrow_mode {
+            return Err(LaunchpadError::EscrowSolOnly.into());
+        }
+        Ok(())
+    }
+
+    /// Set per accepted currency by the auction owner
+    fn ticket_price(&self, _auction: &Auction, _clock: &Clock) -> Result<u64> {
+        Ok(self.payment_option.price_per_ticket)
+    }
+
+    /// The vault receives the payment less any transfer fee
+    fn collect(&self, buyer: &AccountInfo<'info>, amount: u64) -> Result<u64> {
+        transfer_checked(
+            &self.bid_token_program,
+            &self.buyer_bid_token_account,
+            &self.bid_token,
+            &self.auction_vault_bid_token_account,
+            buyer,
+            &[],
+            amount,
+            self.bid_mint.decimals,
+        )?;
+        self.bid_mint.received(amount)
+    }
+
+    fn record(
+        &mut self,
+        _auction: &mut Auction,
+        _buyer_pda: &mut Buyer,
+        _paid: u64,
+        received: u64,
+        referral_reward: u64,
+    ) -> Result<()> {
+        let payment_option = &mut self.payment_option;
+        payment_option.referral_reserved = payment_option
+            .referral_reserved
+            .checked_add(referral_reward)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        payment_option.total_collected = payment_option
+            .total_collected
+            .checked_add(received - referral_reward)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        payment_option.exit(&crate::ID)
+    }
+}
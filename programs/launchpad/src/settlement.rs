@@ -0,0 +1,227 @@
+//! Settlement of an ended auction, by its creator through `withdraw_funds`
+//! or by anyone through `finalize_auction`
+
+use crate::{
+    error::LaunchpadError,
+    events::UnsoldTokensSettled,
+    state::{Auction, Config, Escrow, PaymentOption, UnsoldTokenPolicy},
+    token_interface::{burn_checked, is_token_program, load_mint, load_token_account, transfer_checked},
+    vault,
+};
+use anchor_lang::prelude::*;
+
+/// Accounts the proceeds and unsold tokens of an auction are moved between
+pub struct Settlement<'a, 'info> {
+    pub auction_vault: &'a AccountInfo<'info>,
+    pub auction_vault_token_account: &'a AccountInfo<'info>,
+    pub auction_token: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    /// Receives the sol and spl proceeds, or becomes the escrow beneficiary
+    pub destination: &'a AccountInfo<'info>,
+    /// Auction token account that receives the unsold tokens, unused when they are burnt
+    pub unsold_token_account: &'a AccountInfo<'info>,
+    pub config: &'a Config,
+    pub fee_treasury: &'a AccountInfo<'info>,
+    /// Only loaded in escrow mode
+    pub escrow: &'a AccountInfo<'info>,
+    /// `[payment_option, auction_vault_bid_token_account, destination_bid_token_account,
+    /// treasury_bid_token_account, bid_token, bid_token_program]` for every accepted currency,
+    /// all of them as the auction is marked finalized afterwards
+    pub payment_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> Settlement<'a, 'info> {
+    /// Handle the unsold tokens by the auction's policy, move the proceeds, less the
+    /// platform fee, out of the vault and mark the auction finalized.
+    ///
+    /// Rewards reserved for referrers are left in the vault for `claim_referral_reward`.
+    /// In escrow mode the SOL proceeds stay in the vault and are released by milestone.
+    /// Anything else left in the vault is swept to the destination, and the vault is
+    /// closed once nothing is owed from it.
+    pub fn settle(&self, auction: &mut Auction, auction_key: Pubkey, now: i64) -> Result<()> {
+        let auction_vault = self.auction_vault;
+        let destination = self.destination;
+        let config = self.config;
+
+        // Generate auction seed
+        let auction_vault_seed: &[&[&[_]]] = &[&[
+            "auction_vault".as_bytes(),
+            auction_key.as_ref(),
+            &[auction.vault_bump],
+        ]];
+
+        // Unsold tokens, along with any rounding residue or tokens sent to the vault directly
+        let remaining_tokens_in_auction_pool =
+            load_token_account(self.auction_vault_token_account)?.amount;
+
+        // Return, burn or transfer them to the treasury if there are any remaining tokens
+        let policy = auction.unsold_token_policy;
+        if remaining_tokens_in_auction_pool > 0 {
+            let decimals = load_mint(self.auction_token)?.decimals;
+            if policy == UnsoldTokenPolicy::Burn {
+                burn_checked(
+                    self.token_program,
+                    self.auction_vault_token_account,
+                    self.auction_token,
+                    auction_vault,
+                    auction_vault_seed,
+                    remaining_tokens_in_auction_pool,
+                    decimals,
+                )?;
+            } else {
+                transfer_checked(
+                    self.token_program,
+                    self.auction_vault_token_account,
+                    self.auction_token,
+                    self.unsold_token_account,
+                    auction_vault,
+                    auction_vault_seed,
+                    remaining_tokens_in_auction_pool,
+                    decimals,
+                )?;
+            }
+        }
+        emit!(UnsoldTokensSettled {
+            auction: auction_key,
+            policy,
+            amount: remaining_tokens_in_auction_pool,
+            token_account: if policy == UnsoldTokenPolicy::Burn {
+                Pubkey::default()
+            } else {
+                self.unsold_token_account.key()
+            },
+        });
+
+        // Transfer the sol collected from buyers, less the platform fee
+        // (referral rewards are not part of sol_collected)
+        let sol_amount = auction.sol_collected;
+        if auction.escrow_mode {
+            // Ensure that the vault holds what is escrowed on top of its other obligations
+            if vault::surplus(auction_vault, auction.sol_referral_reserved)? < sol_amount {
+                return Err(LaunchpadError::InsufficientVaultBalance.into());
+            }
+
+            // Hand the sol over to the escrow, the fee is taken as tranches are released
+            let mut escrow: Account<Escrow> = Account::try_from(self.escrow)?;
+            if escrow.funded {
+                return Err(LaunchpadError::EscrowActive.into());
+            }
+            escrow.funded = true;
+            escrow.beneficiary = destination.key();
+            escrow.sol_total = sol_amount;
+            escrow.sol_paid_total = auction.sol_paid_total;
+            escrow.exit(&crate::ID)?;
+
+            // Nothing was raised, so there is nothing to release
+            auction.escrow_mode = sol_amount > 0;
+        } else if sol_amount > 0 {
+            let sol_fee = auction.fee(sol_amount)?;
+            vault::pay_out(auction_vault, destination, sol_amount - sol_fee)?;
+            vault::pay_out(auction_vault, self.fee_treasury, sol_fee)?;
+        }
+
+        // Transfer the spl collected from buyers in every accepted currency, less the platform fee
+        let payment_accounts = self.payment_accounts.chunks_exact(6);
+
+        // Ensure that every accepted currency is settled, nothing can be taken out afterwards
+        if !payment_accounts.remainder().is_empty()
+            || payment_accounts.len() != auction.payment_option_count as usize
+        {
+            return Err(LaunchpadError::InvalidPaymentAccounts.into());
+        }
+        let mut settled_options = Vec::with_capacity(payment_accounts.len());
+        for accounts in payment_accounts {
+            let mut payment_option: Account<PaymentOption> = Account::try_from(&accounts[0])?;
+
+            // Ensure that no currency is passed twice in place of another
+            if settled_options.contains(&payment_option.key()) {
+                return Err(LaunchpadError::InvalidPaymentAccounts.into());
+            }
+            settled_options.push(payment_option.key());
+            let vault_bid_token_account = load_token_account(&accounts[1])?;
+            let destination_bid_token_account = load_token_account(&accounts[2])?;
+            let treasury_bid_token_account = load_token_account(&accounts[3])?;
+            let (bid_token, bid_token_program) = (&accounts[4], &accounts[5]);
+
+            // Ensure that the payment accounts belong to this auction, the destination and the treasury
+            if payment_option.auction != auction_key
+                || vault_bid_token_account.owner != auction_vault.key()
+                || vault_bid_token_account.mint != payment_option.mint
+                || destination_bid_token_account.owner != destination.key()
+                || destination_bid_token_account.mint != payment_option.mint
+                || treasury_bid_token_account.owner != config.fee_treasury
+                || treasury_bid_token_account.mint != payment_option.mint
+                || bid_token.key() != payment_option.mint
+                || !is_token_program(bid_token_program.key)
+            {
+                return Err(LaunchpadError::InvalidPaymentAccounts.into());
+            }
+
+            // Everything but the referrers' rewards goes out, the fee is taken on the tracked proceeds
+            let spl_amount = vault_bid_token_account
+                .amount
+                .checked_sub(payment_option.referral_reserved)
+                .ok_or(LaunchpadError::InsufficientVaultBalance)?;
+            if spl_amount > 0 {
+                let spl_fee = auction.fee(payment_option.total_collected.min(spl_amount))?;
+                let decimals = load_mint(bid_token)?.decimals;
+
+                for (to, amount) in [(&accounts[2], spl_amount - spl_fee), (&accounts[3], spl_fee)] {
+                    if amount == 0 {
+                        continue;
+                    }
+                    transfer_checked(
+                        bid_token_program,
+                        &accounts[1],
+                        bid_token,
+                        to,
+                        auction_vault,
+                        auction_vault_seed,
+                        amount,
+                        decimals,
+                    )?;
+                }
+
+                payment_option.total_collected = 0;
+                payment_option.exit(&crate::ID)?;
+            }
+        }
+
+        // Sweep lamports sent to the vault outside of buys, or close it when nothing is owed
+        // from it anymore, returning its rent to the destination
+        let sol_owed = if auction.escrow_mode {
+            auction
+                .sol_referral_reserved
+                .checked_add(sol_amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        } else {
+            auction.sol_referral_reserved
+        };
+        if sol_owed == 0 {
+            vault::close(auction_vault, destination)?;
+        } else {
+            let residual = vault::surplus(auction_vault, sol_owed)?;
+            if residual > 0 {
+                vault::pay_out(auction_vault, destination, residual)?;
+            }
+        }
+
+        // Reset the auction state
+        auction.owner = Pubkey::default();
+        auction.pending_owner = Pubkey::default();
+        auction.enabled = false;
+        auction.fixed_amount = false;
+        auction.start_time = 0;
+        auction.end_time = 0;
+        auction.tokens_in_pool = 0;
+        auction.remaining_tokens = 0;
+        auction.pay_with_native = false;
+        auction.pre_sale = false;
+        auction.pre_sale_start_time = 0;
+        auction.pre_sale_end_time = 0;
+        auction.sol_collected = 0;
+        auction.sol_paid_total = 0;
+        auction.finalized_at = now;
+        Ok(())
+    }
+}
@@ -1,10 +1,34 @@
 use anchor_lang::prelude::*;
+use crate::error::LaunchpadError;
+use super::{GateKind, MAX_FEE_BPS};
+
+/// Number of buyer tiers with their own purchase delay
+pub const MAX_TIERS: usize = 4;
+
+/// Maximum length of `Auction.name` in bytes
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Layout version of newly created auctions, see `migrate_auction`
+pub const AUCTION_VERSION: u8 = 8;
+
+/// Grace period after the sale ends before anyone can finalize auctions migrated from
+/// before version 3, which had none
+pub const DEFAULT_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+/// What happens to the tokens left in the vault when the auction is settled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum UnsoldTokenPolicy {
+    #[default]
+    Return,      // to the creator
+    Burn,        // burnt from the vault
+    TransferTo,  // to the fixed unsold_destination
+}
 
 #[account]
 #[derive(Default, Debug)]
 pub struct Auction {
     pub owner: Pubkey,
-    pub name: String,
+    pub name: String,  // display name, at most MAX_NAME_LEN bytes
     pub enabled: bool,
     pub fixed_amount: bool,
     pub start_time: i64,
@@ -17,4 +41,165 @@ pub struct Auction {
     pub remaining_tokens: u64,
     pub token_quantity_per_ticket: u64,  // no. of tokens in one ticket
     pub funding_demand: u64, // in SOL (return on investment)
+    pub sol_collected: u64,  // lamports collected from SOL purchases
+    pub usd_denominated: bool,
+    pub usd_ticket_price: u64,  // in USD with 6 decimals, converted to SOL at purchase
+    pub price_feed: Pubkey,     // SOL/USD feed used for usd_denominated auctions
+    pub max_price_age: i64,     // in seconds
+    pub max_price_confidence_bps: u16,
+    pub pending_owner: Pubkey,  // proposed owner, until accepted
+    pub tier_delays: [u32; MAX_TIERS],  // seconds after the sale starts before each tier can buy
+    pub max_buys_per_slot: u32,  // across all buyers, 0 for no limit
+    pub last_buy_slot: u64,
+    pub buys_in_slot: u32,
+    pub registration_required: bool,  // buyers must register within the registration window
+    pub registration_start: i64,
+    pub registration_end: i64,
+    pub registered_count: u64,  // no. of registered buyers
+    pub kyc_authority: Pubkey,  // signs pre-sale KYC attestations, default if unused
+    pub gate_kind: GateKind,
+    pub gate_mint: Pubkey,  // gating SPL mint or NFT collection mint
+    pub gate_min_amount: u64,  // for SplBalance gates, in base units
+    pub gate_tier: u8,  // tier of buyers admitted through the gate
+    pub referral_bps: u16,  // share of referred proceeds reserved for the referrer
+    pub sol_referral_reserved: u64,  // lamports reserved for referrers, kept out of sol_collected
+    pub liquidity_bps: u16,  // share of sol_collected seeded into the liquidity pool
+    pub liquidity_tokens: u64,  // tokens seeded into the liquidity pool, on top of tokens_in_pool
+    pub lp_unlock_time: i64,  // until which the LP tokens stay locked
+    pub liquidity_added: bool,
+    pub team_allocation: u64,  // no. of tokens locked in the team vault, see TeamVesting
+    pub escrow_mode: bool,  // SOL proceeds are released by milestones, see Escrow
+    pub sol_paid_total: u64,  // lamports paid by buyers, before referral rewards
+    pub bump: u8,  // of the auction PDA, stored at init
+    pub vault_bump: u8,  // of the auction_vault PDA, signs for the vault
+    pub id: u64,  // assigned from Config.auction_count, seeds the auction PDA
+    pub version: u8,  // AUCTION_VERSION when created or last migrated
+    pub buyer_registry: Pubkey,  // BuyerRegistry listing every buyer, default if none (version 2)
+    pub proceeds_destination: Pubkey,  // receives the proceeds when finalized by anyone (version 3)
+    pub unsold_destination: Pubkey,  // receives the unsold tokens when finalized by anyone, or always with TransferTo
+    pub grace_period: i64,  // seconds after end_time the owner has to withdraw before anyone can finalize
+    pub finalized_at: i64,  // when the proceeds were withdrawn or finalized, 0 until then
+    pub unsold_token_policy: UnsoldTokenPolicy,  // set at init (version 4)
+    pub name_seeded: bool,  // PDA seeded by the name, for auctions created before ids (version 5)
+    pub payment_option_count: u8,  // no. of PaymentOptions, all settled together (version 6)
+    pub fee_bps: u16,  // platform fee taken from the proceeds, Config.fee_bps at init (version 7)
+    pub token_mint: Pubkey,  // mint of the tokens on sale, stored by add_token (version 8)
+    pub reserved: [u64; 10],  // room for new fields, taken from the end
+}
+
+impl Auction {
+    /// Serialized size of the account without the discriminator, with room for the longest name
+    pub const INIT_SPACE: usize = 32  // owner
+        + 4 + MAX_NAME_LEN  // name
+        + 1 + 1  // enabled, fixed_amount
+        + 8 + 8  // start_time, end_time
+        + 1 + 1  // pay_with_native, pre_sale
+        + 8 + 8  // pre_sale_start_time, pre_sale_end_time
+        + 8 * 5  // tokens_in_pool, remaining_tokens, token_quantity_per_ticket, funding_demand, sol_collected
+        + 1 + 8 + 32 + 8 + 2  // usd_denominated, usd_ticket_price, price_feed, max_price_age, max_price_confidence_bps
+        + 32  // pending_owner
+        + 4 * MAX_TIERS  // tier_delays
+        + 4 + 8 + 4  // max_buys_per_slot, last_buy_slot, buys_in_slot
+        + 1 + 8 + 8 + 8  // registration_required, registration_start, registration_end, registered_count
+        + 32  // kyc_authority
+        + 1 + 32 + 8 + 1  // gate_kind, gate_mint, gate_min_amount, gate_tier
+        + 2 + 8  // referral_bps, sol_referral_reserved
+        + 2 + 8 + 8 + 1  // liquidity_bps, liquidity_tokens, lp_unlock_time, liquidity_added
+        + 8  // team_allocation
+        + 1 + 8  // escrow_mode, sol_paid_total
+        + 1 + 1  // bump, vault_bump
+        + 8  // id
+        + 1  // version
+        + 32  // buyer_registry
+        + 32 + 32 + 8 + 8  // proceeds_destination, unsold_destination, grace_period, finalized_at
+        + 1  // unsold_token_policy
+        + 1  // name_seeded
+        + 1  // payment_option_count
+        + 2  // fee_bps
+        + 32  // token_mint
+        + 8 * 10;  // reserved
+
+    /// Fill in the fields added since the account's version. Fields without a default
+    /// below are zero, which the realloc in `migrate_auction` already wrote.
+    /// Auctions from before version 7 take the current platform fee, `fee_bps`
+    pub fn migrate(&mut self, now: i64, fee_bps: u16) {
+        if self.version < 3 {
+            self.proceeds_destination = self.owner;
+            self.unsold_destination = self.owner;
+            self.grace_period = DEFAULT_GRACE_PERIOD;
+            // Withdrawing reset the owner, so that it can't be finalized again
+            if self.owner == Pubkey::default() {
+                self.finalized_at = now;
+            }
+        }
+        if self.version < 7 {
+            self.fee_bps = fee_bps;
+        }
+        self.version = AUCTION_VERSION;
+    }
+
+    /// Platform fee owed on `amount` of proceeds, at the fee the auction was created with
+    pub fn fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128) * (self.fee_bps as u128) / (MAX_FEE_BPS as u128);
+        u64::try_from(fee).map_err(|_| ProgramError::ArithmeticOverflow.into())
+    }
+
+    /// Seed of the auction PDA after the `b"auction"` prefix: the id, or the name for
+    /// auctions created before ids were assigned
+    pub fn pda_seed(&self) -> Vec<u8> {
+        if self.name_seeded {
+            self.name.as_bytes().to_vec()
+        } else {
+            self.id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// Owner of the token account that receives the unsold tokens when withdrawn to
+    /// `destination`, unless they are burnt
+    pub fn unsold_recipient(&self, destination: Pubkey) -> Pubkey {
+        match self.unsold_token_policy {
+            UnsoldTokenPolicy::TransferTo => self.unsold_destination,
+            UnsoldTokenPolicy::Return | UnsoldTokenPolicy::Burn => destination,
+        }
+    }
+
+    /// Time at which buying opens, the pre-sale start if there is one
+    pub fn sale_start_time(&self) -> i64 {
+        if self.pre_sale {
+            self.pre_sale_start_time
+        } else {
+            self.start_time
+        }
+    }
+
+    /// Enforce the anti-bot limits for a buyer of `tier` in the pre-sale or public
+    /// sale, and record the purchase in the current slot
+    pub fn check_bot_limits(&mut self, tier: u8, in_pre_sale: bool, clock: &Clock) -> Result<()> {
+        let phase_start_time = if in_pre_sale {
+            self.pre_sale_start_time
+        } else {
+            self.start_time
+        };
+
+        // Ensure that the buyer's tier has waited long enough since the sale started
+        let delay = *self
+            .tier_delays
+            .get(tier as usize)
+            .ok_or(LaunchpadError::InvalidTier)? as i64;
+        if clock.unix_timestamp < phase_start_time.saturating_add(delay) {
+            return Err(LaunchpadError::TierCooldown.into());
+        }
+
+        if self.last_buy_slot != clock.slot {
+            self.last_buy_slot = clock.slot;
+            self.buys_in_slot = 0;
+        }
+
+        // Ensure that the purchases in this slot don't exceed the limit
+        if self.max_buys_per_slot > 0 && self.buys_in_slot >= self.max_buys_per_slot {
+            return Err(LaunchpadError::SlotPurchaseLimit.into());
+        }
+        self.buys_in_slot += 1;
+        Ok(())
+    }
 }
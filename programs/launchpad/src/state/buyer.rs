@@ -4,4 +4,11 @@ use anchor_lang::prelude::*;
 #[derive(Default, Debug)]
 pub struct Buyer {
     pub participate: bool,
+    pub registered_at: i64,
+    pub wallet: Pubkey,
+    pub auction: Pubkey,
+    pub tickets: u32,  // tickets bought, the weight of milestone votes
+    pub sol_paid: u64,  // lamports paid for tickets, refunded pro rata from an escrow
+    pub refunded: bool,
+    pub registry_slot: u32,  // index + 1 of the buyer in the BuyerRegistry, 0 if not listed
 }
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::error::LaunchpadError;
+use super::Buyer;
+
+/// Maximum no. of buyers listed in a `BuyerRegistry`
+pub const REGISTRY_CAPACITY: usize = 2048;
+
+/// Every buyer of an auction in buying order, so that settlement can walk them in batches.
+/// Allocated by the client and attached with `init_buyer_registry` before the sale starts,
+/// to auctions with no more tickets than `REGISTRY_CAPACITY`.
+#[account(zero_copy)]
+pub struct BuyerRegistry {
+    pub auction: Pubkey,
+    pub count: u64,  // no. of entries in use
+    pub entries: [RegistryEntry; REGISTRY_CAPACITY],
+}
+
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct RegistryEntry {
+    pub buyer: Pubkey,
+    pub tickets: u64,
+    pub sol_paid: u64,  // lamports, as in Buyer.sol_paid
+    pub token_paid: u64,  // base units of paid_mint
+    pub paid_mint: Pubkey,  // bid token the buyer pays in, default until their first SPL buy
+}
+
+impl BuyerRegistry {
+    /// Mirror the buyer's purchases into their entry, appending one on their first buy,
+    /// and add `paid` in `mint`, the default key for SOL. A listed buyer pays in SOL and
+    /// at most one bid token.
+    pub fn record(&mut self, buyer_pda: &mut Buyer, mint: Pubkey, paid: u64) -> Result<()> {
+        let index = match buyer_pda.registry_slot {
+            0 => {
+                let index = self.count as usize;
+                if index >= REGISTRY_CAPACITY {
+                    return Err(LaunchpadError::BuyerRegistryFull.into());
+                }
+                self.count += 1;
+                self.entries[index].buyer = buyer_pda.wallet;
+                buyer_pda.registry_slot = self.count as u32;
+                index
+            }
+            slot => slot as usize - 1,
+        };
+
+        let entry = &mut self.entries[index];
+        entry.tickets = buyer_pda.tickets as u64;
+        if mint == Pubkey::default() {
+            entry.sol_paid = buyer_pda.sol_paid;
+            return Ok(());
+        }
+
+        // Ensure that the bid token amounts paid add up in a single currency
+        if entry.paid_mint == Pubkey::default() {
+            entry.paid_mint = mint;
+        } else if entry.paid_mint != mint {
+            return Err(LaunchpadError::PaymentMintMismatch.into());
+        }
+        entry.token_paid = entry
+            .token_paid
+            .checked_add(paid)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::error::LaunchpadError;
+use super::STAKE_TIERS;
+
+/// Maximum value of `Config.fee_bps` (100%)
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Instruction classes that can be paused on their own through `Config.pause_flags`
+pub const PAUSE_BUYS: u8 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_WHITELIST: u8 = 1 << 2;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub fee_bps: u16,  // platform fee of new auctions, see Auction.fee_bps
+    pub fee_treasury: Pubkey,
+    pub paused: bool,
+    pub listing_fee: u64,  // in lamports, charged on init_auction
+    pub pause_flags: u8,  // PAUSE_* instruction classes that are paused
+    pub pending_admin: Pubkey,  // proposed admin, until accepted
+    pub stake_mint: Pubkey,  // platform token staked for allocation tiers
+    pub stake_lock_period: i64,  // in seconds, from the last stake
+    pub stake_thresholds: [u64; STAKE_TIERS],  // minimum stake of each tier, 0 if unused
+    pub stake_tickets: [u32; STAKE_TIERS],  // pre-sale tickets of each tier
+    pub total_staked: u64,
+    pub auction_count: u64,  // no. of auctions created, the id of the next one
+    pub stake_vault_bump: u8,  // of the stake_vault PDA, stored by configure_staking
+    pub bump: u8,  // of the config PDA, stored at init
+}
+
+impl Config {
+    /// Best staking tier reached by `amount` and its ticket allocation
+    pub fn stake_tier(&self, amount: u64) -> Option<(u8, u32)> {
+        (0..STAKE_TIERS)
+            .find(|&tier| self.stake_thresholds[tier] > 0 && amount >= self.stake_thresholds[tier])
+            .map(|tier| (tier as u8, self.stake_tickets[tier]))
+    }
+
+    /// Ensure that neither the program nor the instruction class (one of `PAUSE_*`,
+    /// or 0 for instructions that are only stopped by a full pause) is paused
+    pub fn check_active(&self, class: u8) -> Result<()> {
+        if self.paused {
+            return Err(LaunchpadError::ProgramPaused.into());
+        }
+
+        match self.pause_flags & class {
+            0 => Ok(()),
+            PAUSE_BUYS => Err(LaunchpadError::BuysPaused.into()),
+            PAUSE_WITHDRAWALS => Err(LaunchpadError::WithdrawalsPaused.into()),
+            _ => Err(LaunchpadError::WhitelistPaused.into()),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DelegateRole {
+    WhitelistManager,
+    Pauser,
+    Withdrawer,
+}
+
+/// Role granted by the auction owner to another wallet
+#[account]
+#[derive(Debug)]
+pub struct Delegate {
+    pub auction: Pubkey,
+    pub delegate: Pubkey,
+    pub role: DelegateRole,
+    pub destination: Pubkey,  // where a Withdrawer sends the funds
+    pub granted_by: Pubkey,   // owner who granted the role, which lapses once ownership moves
+}
+
+/// Load the delegate PDA granting `role` on `auction` to `signer`, if there is one
+/// and it was granted by the auction's current `owner`
+pub fn load_delegate(
+    delegate: &AccountInfo,
+    auction: Pubkey,
+    owner: Pubkey,
+    signer: Pubkey,
+    role: DelegateRole,
+) -> Option<Delegate> {
+    let delegate: Account<Delegate> = Account::try_from(delegate).ok()?;
+    if delegate.auction != auction
+        || delegate.granted_by != owner
+        || delegate.delegate != signer
+        || delegate.role != role
+    {
+        return None;
+    }
+    Some(delegate.into_inner())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::{error::LaunchpadError, vault};
+use super::{Auction, MAX_FEE_BPS};
+
+/// Maximum number of milestones of an escrow
+pub const MAX_MILESTONES: usize = 4;
+
+/// SOL proceeds of an auction held in the auction vault and released in tranches
+/// as milestones are approved, or refunded to buyers once a milestone is rejected
+#[account]
+#[derive(Default, Debug)]
+pub struct Escrow {
+    pub auction: Pubkey,
+    pub approver: Pubkey,  // approves milestones, default for buyer votes weighted by tickets
+    pub vote_period: i64,  // in seconds
+    pub tranche_bps: [u16; MAX_MILESTONES],  // share of the escrow released at each milestone
+    pub milestone_count: u8,
+    pub current_milestone: u8,
+    pub beneficiary: Pubkey,  // receives the released tranches
+    pub funded: bool,
+    pub rejected: bool,
+    pub sol_total: u64,  // lamports escrowed by withdraw_funds
+    pub sol_released: u64,
+    pub sol_paid_total: u64,  // lamports paid by buyers, the base of refunds
+    pub vote_end: i64,  // 0 until the current milestone is requested
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub bump: u8,
+    pub vault_bump: u8,  // copied from the auction, signs for the vault
+}
+
+impl Escrow {
+    /// Whether every milestone has been released or one was rejected
+    pub fn finished(&self) -> bool {
+        self.rejected || self.current_milestone >= self.milestone_count
+    }
+
+    /// Release the current milestone's tranche to the beneficiary, less the auction's
+    /// platform fee, and move on to the next milestone
+    pub fn release<'info>(
+        &mut self,
+        auction: &Auction,
+        auction_vault: &AccountInfo<'info>,
+        beneficiary: &AccountInfo<'info>,
+        fee_treasury: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if !self.funded || self.finished() {
+            return Err(LaunchpadError::InvalidMilestone.into());
+        }
+
+        // The last tranche takes the rounding remainder
+        let milestone = self.current_milestone as usize;
+        let amount = if milestone + 1 == self.milestone_count as usize {
+            self.sol_total - self.sol_released
+        } else {
+            ((self.sol_total as u128) * (self.tranche_bps[milestone] as u128)
+                / (MAX_FEE_BPS as u128)) as u64
+        };
+        let fee = auction.fee(amount)?;
+
+        vault::pay_out(auction_vault, beneficiary, amount - fee)?;
+        vault::pay_out(auction_vault, fee_treasury, fee)?;
+
+        self.sol_released += amount;
+        self.current_milestone += 1;
+        self.vote_end = 0;
+        self.votes_for = 0;
+        self.votes_against = 0;
+        Ok(())
+    }
+
+    /// Refund owed to a buyer that paid `sol_paid`, out of what is left in escrow
+    pub fn refund(&self, sol_paid: u64) -> u64 {
+        let remaining = self.sol_total - self.sol_released;
+        ((remaining as u128) * (sol_paid as u128) / (self.sol_paid_total.max(1) as u128)) as u64
+    }
+}
+
+/// Marks that a buyer voted on a milestone
+#[account]
+#[derive(Default, Debug)]
+pub struct MilestoneVote {
+    pub approve: bool,
+    pub weight: u64,
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Holding that makes a buyer eligible for the pre-sale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GateKind {
+    #[default]
+    None,
+    SplBalance,     // at least gate_min_amount of gate_mint
+    NftCollection,  // an NFT of the verified collection gate_mint
+}
+
+/// Marks a gating NFT as used by one wallet in an auction
+#[account]
+#[derive(Default, Debug)]
+pub struct GateMarker {
+    pub auction: Pubkey,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// Maximum length of each `AuctionMetadata` URI and social link in bytes
+pub const MAX_URI_LEN: usize = 200;
+
+/// Maximum no. of `AuctionMetadata.social_links`
+pub const MAX_SOCIAL_LINKS: usize = 5;
+
+/// Project details shown by frontends, kept apart from the `Auction` so that it can be
+/// resized to its contents until the sale starts
+#[account]
+#[derive(Default, Debug)]
+pub struct AuctionMetadata {
+    pub auction: Pubkey,
+    pub description_uri: String,
+    pub logo_uri: String,
+    pub website: String,
+    pub social_links: Vec<String>,
+    pub content_hash: [u8; 32],  // hash of the content behind description_uri
+    pub bump: u8,
+}
+
+impl AuctionMetadata {
+    /// Serialized size of the account without the discriminator, with empty strings
+    pub const BASE_SPACE: usize = 32  // auction
+        + 4 + 4 + 4  // description_uri, logo_uri, website
+        + 4  // social_links
+        + 32  // content_hash
+        + 1;  // bump
+}
@@ -1,7 +1,31 @@
 pub mod auction;
 pub mod buyer;
+pub mod buyer_registry;
+pub mod config;
+pub mod delegate;
+pub mod escrow;
+pub mod gate;
+pub mod metadata;
+pub mod payment_option;
+pub mod pool;
+pub mod price_feed;
+pub mod referral;
+pub mod stake;
+pub mod team_vesting;
 pub mod whitelist;
 
 pub use auction::*;
 pub use buyer::*;
+pub use buyer_registry::*;
+pub use config::*;
+pub use delegate::*;
+pub use escrow::*;
+pub use gate::*;
+pub use metadata::*;
+pub use payment_option::*;
+pub use pool::*;
+pub use price_feed::*;
+pub use referral::*;
+pub use stake::*;
+pub use team_vesting::*;
 pub use whitelist::*;
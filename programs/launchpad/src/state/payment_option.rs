@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct PaymentOption {
+    pub auction: Pubkey,
+    pub mint: Pubkey,           // bid token accepted as payment
+    pub price_per_ticket: u64,  // in bid token base units
+    pub total_collected: u64,   // bid tokens collected and not yet withdrawn
+    pub referral_reserved: u64, // bid tokens reserved for referrers, kept out of total_collected
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::error::LaunchpadError;
+use super::MAX_FEE_BPS;
+
+/// Swap fee of the liquidity pools, kept in the reserves for liquidity providers
+pub const POOL_FEE_BPS: u16 = 30;
+
+/// Constant-product SOL/token pool seeded from an auction's proceeds.
+/// SOL is held in the pool PDA itself, tokens in its associated token account.
+#[account]
+#[derive(Default, Debug)]
+pub struct Pool {
+    pub auction: Pubkey,
+    pub token_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub sol_reserve: u64,    // in lamports, above the pool's rent
+    pub token_reserve: u64,
+    pub lp_supply: u64,
+    pub lp_owner: Pubkey,    // receives the locked LP tokens once unlocked
+    pub lp_unlock_time: i64,
+    pub lp_claimed: bool,
+    pub bump: u8,  // of the pool PDA, signs for its reserves and LP tokens
+}
+
+impl Pool {
+    /// Output of swapping `amount_in` into the pool, given the reserves in and out
+    pub fn swap_output(amount_in: u64, reserve_in: u64, reserve_out: u64) -> Result<u64> {
+        let in_after_fee = (amount_in as u128) * ((MAX_FEE_BPS - POOL_FEE_BPS) as u128);
+        let numerator = in_after_fee * (reserve_out as u128);
+        let denominator = (reserve_in as u128) * (MAX_FEE_BPS as u128) + in_after_fee;
+        let out = numerator
+            .checked_div(denominator)
+            .ok_or(LaunchpadError::InsufficientLiquidity)?;
+        u64::try_from(out).map_err(|_| ProgramError::ArithmeticOverflow.into())
+    }
+
+    /// Share of a reserve owed for `lp_amount` of the LP supply
+    pub fn share(&self, reserve: u64, lp_amount: u64) -> Result<u64> {
+        let share = (reserve as u128) * (lp_amount as u128)
+            / (self.lp_supply.max(1) as u128);
+        u64::try_from(share).map_err(|_| ProgramError::ArithmeticOverflow.into())
+    }
+}
+
+/// LP tokens minted for the initial reserves, sqrt(sol * tokens)
+pub fn initial_lp_supply(sol_amount: u64, token_amount: u64) -> u64 {
+    let product = (sol_amount as u128) * (token_amount as u128);
+    if product == 0 {
+        return 0;
+    }
+    // Newton's method, converging from above
+    let mut x = product;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + product / x) / 2;
+    }
+    x as u64
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Price feed account owned by this program, written by its authority.
+/// Lets local validators and tests quote prices without a live oracle, only
+/// created and read by builds with the `mock-oracle` feature.
+#[account]
+#[derive(Default, Debug)]
+pub struct MockPriceFeed {
+    pub authority: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
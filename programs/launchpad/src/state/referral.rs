@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::error::LaunchpadError;
+use super::MAX_FEE_BPS;
+
+/// Purchases referred by a wallet in one auction and currency, the default mint for SOL
+#[account]
+#[derive(Default, Debug)]
+pub struct Referral {
+    pub auction: Pubkey,
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub volume: u64,   // referred proceeds
+    pub reward: u64,   // reserved in the auction vault and not yet claimed
+    pub claimed: u64,
+}
+
+/// Record a purchase of `amount` referred by `referrer`, if any, in the referral PDA
+/// and return the reward to reserve out of the proceeds
+pub fn track_referral<'info>(
+    referral: &AccountInfo<'info>,
+    auction: Pubkey,
+    mint: Pubkey,
+    referrer: Option<Pubkey>,
+    buyer: Pubkey,
+    amount: u64,
+    referral_bps: u16,
+) -> Result<u64> {
+    let Some(referrer) = referrer else {
+        return Ok(0);
+    };
+
+    // Ensure that the referral belongs to the referrer, auction and currency
+    let mut referral: Account<Referral> =
+        Account::try_from(referral).map_err(|_| LaunchpadError::InvalidReferral)?;
+    if referrer == buyer
+        || referral.referrer != referrer
+        || referral.auction != auction
+        || referral.mint != mint
+    {
+        return Err(LaunchpadError::InvalidReferral.into());
+    }
+
+    let reward = ((amount as u128) * (referral_bps as u128) / (MAX_FEE_BPS as u128)) as u64;
+    referral.volume = referral
+        .volume
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    referral.reward = referral
+        .reward
+        .checked_add(reward)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    referral.exit(&crate::ID)?;
+    Ok(reward)
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::error::LaunchpadError;
+use super::MAX_TIERS;
+
+/// Number of tiers reachable by staking, the last tier is left to public buyers
+pub const STAKE_TIERS: usize = MAX_TIERS - 1;
+
+/// Platform tokens staked by a wallet in the stake vault
+#[account]
+#[derive(Default, Debug)]
+pub struct Stake {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub locked_until: i64,  // unstaking is possible from this time
+}
+
+/// Pre-sale allocation of a staker in one auction, from a snapshot of their stake
+#[account]
+#[derive(Default, Debug)]
+pub struct StakeAllocation {
+    pub auction: Pubkey,
+    pub staker: Pubkey,
+    pub tier: u8,
+    pub tickets: u32,  // no. of pre-sale tickets the staker can buy
+    pub used: u32,
+}
+
+impl StakeAllocation {
+    /// Use the tickets left in the allocation and return how many they are
+    pub fn take_tickets(&mut self) -> Result<u32> {
+        if self.used >= self.tickets {
+            return Err(LaunchpadError::AllocationExhausted.into());
+        }
+        let tickets = self.tickets - self.used;
+        self.used = self.tickets;
+        Ok(tickets)
+    }
+}
+
+/// Load the stake allocation PDA of `staker` in `auction`, if there is one
+pub fn load_stake_allocation<'info>(
+    allocation: &AccountInfo<'info>,
+    auction: Pubkey,
+    staker: Pubkey,
+) -> Option<Account<'info, StakeAllocation>> {
+    let allocation: Account<StakeAllocation> = Account::try_from(allocation).ok()?;
+    if allocation.auction != auction || allocation.staker != staker {
+        return None;
+    }
+    Some(allocation)
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+/// Team allocation of an auction, locked in the team vault and released linearly
+/// from the end of the sale to `end_time`, with nothing claimable before `cliff_time`
+#[account]
+#[derive(Default, Debug)]
+pub struct TeamVesting {
+    pub auction: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,    // in base units
+    pub claimed: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub bump: u8,  // of the team_vesting PDA
+    pub vault_bump: u8,  // of the team_vault PDA, signs for the vault
+}
+
+impl TeamVesting {
+    /// Amount vested at `now`, claimed or not
+    pub fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff_time {
+            0
+        } else if now >= self.end_time {
+            self.total
+        } else {
+            let elapsed = (now - self.start_time) as u128;
+            let duration = (self.end_time - self.start_time) as u128;
+            ((self.total as u128) * elapsed / duration) as u64
+        }
+    }
+}
@@ -1,7 +1,28 @@
 use anchor_lang::prelude::*;
+use super::MAX_TIERS;
+
+/// Tier of buyers that aren't whitelisted
+pub const PUBLIC_TIER: u8 = (MAX_TIERS - 1) as u8;
+
+/// Size of whitelist PDAs created before tiers, grown by `migrate_auction`
+pub const LEGACY_WHITELIST_LEN: usize = 8 + 1;
 
 #[account]
 #[derive(Default, Debug)]
 pub struct Whitelist {
     pub whitelisted: bool,
+    pub tier: u8,  // index into Auction.tier_delays
+}
+
+/// Tier of the buyer if this whitelist PDA exists and whitelists them
+pub fn whitelisted_tier(whitelist: &AccountInfo) -> Option<u8> {
+    match Account::<Whitelist>::try_from(whitelist) {
+        Ok(whitelist) if whitelist.whitelisted => Some(whitelist.tier),
+        _ => None,
+    }
+}
+
+/// Tier of the buyer with this whitelist PDA, which may not exist for public buyers
+pub fn buyer_tier(whitelist: &AccountInfo) -> u8 {
+    whitelisted_tier(whitelist).unwrap_or(PUBLIC_TIER)
 }
@@ -0,0 +1,195 @@
+//! Pre-sale eligibility by holding a gating SPL balance or collection NFT
+
+use crate::{
+    error::LaunchpadError,
+    state::{Auction, GateKind, GateMarker},
+    token_interface::load_token_account,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+/// Metaplex token metadata program
+pub mod token_metadata {
+    anchor_lang::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+#[derive(AnchorDeserialize)]
+struct Creator {
+    _address: Pubkey,
+    _verified: bool,
+    _share: u8,
+}
+
+#[derive(AnchorDeserialize)]
+struct Collection {
+    verified: bool,
+    key: Pubkey,
+}
+
+/// Leading fields of a Metaplex metadata account, up to the collection
+#[derive(AnchorDeserialize)]
+struct MetadataPrefix {
+    _key: u8,
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    _seller_fee_basis_points: u16,
+    _creators: Option<Vec<Creator>>,
+    _primary_sale_happened: bool,
+    _is_mutable: bool,
+    _edition_nonce: Option<u8>,
+    _token_standard: Option<u8>,
+    collection: Option<Collection>,
+}
+
+/// Check that `buyer` holds the auction's gating token and return the gate tier.
+/// The gating token may belong to either token program.
+///
+/// The gate accounts are passed as `remaining_accounts`: `[gate_token_account]` for
+/// SPL balance gates and `[gate_token_account, nft_metadata, gate_marker]` for NFT
+/// collection gates, where the marker PDA stops one NFT from admitting several wallets.
+pub fn verify_token_gate<'info>(
+    auction: &Auction,
+    auction_key: Pubkey,
+    buyer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    gate_accounts: &[AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<u8> {
+    let gate_token_account = gate_accounts
+        .first()
+        .ok_or(LaunchpadError::InvalidGateAccounts)?;
+    let token_account = load_token_account(gate_token_account)
+        .map_err(|_| LaunchpadError::InvalidGateAccounts)?;
+    if token_account.owner != buyer.key() {
+        return Err(LaunchpadError::InvalidGateAccounts.into());
+    }
+
+    match auction.gate_kind {
+        GateKind::None => Err(LaunchpadError::NotWhitelisted.into()),
+        GateKind::SplBalance => {
+            if token_account.mint != auction.gate_mint
+                || token_account.amount < auction.gate_min_amount
+            {
+                return Err(LaunchpadError::NotWhitelisted.into());
+            }
+            Ok(auction.gate_tier)
+        }
+        GateKind::NftCollection => {
+            let [_, metadata, marker] = gate_accounts else {
+                return Err(LaunchpadError::InvalidGateAccounts.into());
+            };
+            if token_account.amount != 1 {
+                return Err(LaunchpadError::NotWhitelisted.into());
+            }
+
+            // Ensure that the metadata belongs to the NFT and to the verified collection
+            let (metadata_key, _) = Pubkey::find_program_address(
+                &[b"metadata", token_metadata::ID.as_ref(), token_account.mint.as_ref()],
+                &token_metadata::ID,
+            );
+            if metadata.key() != metadata_key || metadata.owner != &token_metadata::ID {
+                return Err(LaunchpadError::InvalidGateAccounts.into());
+            }
+            let data = metadata.try_borrow_data()?;
+            let nft = MetadataPrefix::deserialize(&mut &data[..])
+                .map_err(|_| LaunchpadError::InvalidGateAccounts)?;
+            let in_collection = matches!(
+                nft.collection,
+                Some(Collection { verified: true, key }) if key == auction.gate_mint
+            );
+            if nft.mint != token_account.mint || !in_collection {
+                return Err(LaunchpadError::NotWhitelisted.into());
+            }
+
+            use_gate_marker(
+                marker,
+                auction_key,
+                token_account.mint,
+                buyer,
+                system_program,
+                program_id,
+            )?;
+            Ok(auction.gate_tier)
+        }
+    }
+}
+
+/// Record `mint` as used by `buyer`, failing if another wallet used it before
+fn use_gate_marker<'info>(
+    marker: &AccountInfo<'info>,
+    auction_key: Pubkey,
+    mint: Pubkey,
+    buyer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let (marker_key, bump) = Pubkey::find_program_address(
+        &[b"gate_marker", auction_key.as_ref(), mint.as_ref()],
+        program_id,
+    );
+    if marker.key() != marker_key {
+        return Err(LaunchpadError::InvalidGateAccounts.into());
+    }
+
+    if marker.owner == program_id {
+        let used: Account<GateMarker> = Account::try_from(marker)?;
+        if used.buyer != buyer.key() {
+            return Err(LaunchpadError::GateTokenUsed.into());
+        }
+        return Ok(());
+    }
+
+    let marker_seed: &[&[&[u8]]] = &[&[b"gate_marker", auction_key.as_ref(), mint.as_ref(), &[bump]]];
+    let space = 8 + std::mem::size_of::<GateMarker>();
+    let rent = Rent::get()?.minimum_balance(space);
+
+    // The marker may have been funded beforehand, which create_account refuses
+    if marker.lamports() == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: buyer.clone(), to: marker.clone() },
+                marker_seed,
+            ),
+            rent,
+            space as u64,
+            program_id,
+        )?;
+    } else {
+        let top_up = rent.saturating_sub(marker.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer { from: buyer.clone(), to: marker.clone() },
+                ),
+                top_up,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate { account_to_allocate: marker.clone() },
+                marker_seed,
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign { account_to_assign: marker.clone() },
+                marker_seed,
+            ),
+            program_id,
+        )?;
+    }
+
+    let mut data = marker.try_borrow_mut_data()?;
+    GateMarker { auction: auction_key, mint, buyer: buyer.key() }.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Token accounts and transfers of both the SPL Token and the Token-2022 program
+
+use crate::error::LaunchpadError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::{Account as TokenAccountState, Mint as MintState},
+};
+
+pub use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// Whether `key` is the SPL Token or the Token-2022 program
+pub fn is_token_program(key: &Pubkey) -> bool {
+    *key == spl_token::ID || *key == spl_token_2022::ID
+}
+
+/// Base fields of a token account of either token program
+#[derive(Clone, Copy, Debug)]
+pub struct TokenAccountData {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Load a token account owned by either token program
+pub fn load_token_account(info: &AccountInfo) -> Result<TokenAccountData> {
+    if !is_token_program(info.owner) {
+        return Err(LaunchpadError::InvalidTokenAccount.into());
+    }
+    let data = info.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)
+        .map_err(|_| LaunchpadError::InvalidTokenAccount)?;
+    Ok(TokenAccountData {
+        mint: account.base.mint,
+        owner: account.base.owner,
+        amount: account.base.amount,
+    })
+}
+
+/// Whether `info` is a token account of `mint` belonging to `owner`, used in account constraints
+pub fn is_token_account(info: &AccountInfo, owner: &Pubkey, mint: &Pubkey) -> bool {
+    matches!(
+        load_token_account(info),
+        Ok(account) if account.owner == *owner && account.mint == *mint
+    )
+}
+
+/// Decimals and transfer fee of a mint of either token program
+#[derive(Clone, Copy, Debug)]
+pub struct MintData {
+    pub decimals: u8,
+    pub transfer_fee: Option<TransferFeeConfig>,
+}
+
+/// Load a mint owned by either token program
+pub fn load_mint(info: &AccountInfo) -> Result<MintData> {
+    if !is_token_program(info.owner) {
+        return Err(LaunchpadError::InvalidTokenAccount.into());
+    }
+    let data = info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)
+        .map_err(|_| LaunchpadError::InvalidTokenAccount)?;
+    Ok(MintData {
+        decimals: mint.base.decimals,
+        transfer_fee: mint.get_extension::<TransferFeeConfig>().ok().copied(),
+    })
+}
+
+impl MintData {
+    /// Fee withheld from a transfer of `amount`, 0 unless the mint has the transfer fee extension
+    pub fn transfer_fee(&self, amount: u64) -> Result<u64> {
+        match &self.transfer_fee {
+            Some(config) => config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or_else(|| ProgramError::InvalidArgument.into()),
+            None => Ok(0),
+        }
+    }
+
+    /// Amount that arrives at the destination of a transfer of `amount`
+    pub fn received(&self, amount: u64) -> Result<u64> {
+        Ok(amount - self.transfer_fee(amount)?)
+    }
+
+    /// Amount to transfer for `received` to arrive at the destination
+    pub fn gross_up(&self, received: u64) -> Result<u64> {
+        let fee = match &self.transfer_fee {
+            Some(config) => config
+                .calculate_inverse_epoch_fee(Clock::get()?.epoch, received)
+                .ok_or(ProgramError::InvalidArgument)?,
+            None => 0,
+        };
+        received
+            .checked_add(fee)
+            .ok_or_else(|| ProgramError::InvalidArgument.into())
+    }
+}
+
+/// `transfer_checked` through whichever token program owns the mint
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    invoke_signed(
+        &ix,
+        &[from.clone(), mint.clone(), to.clone(), authority.clone(), token_program.clone()],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// `burn_checked` through whichever token program owns the mint
+pub fn burn_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::burn_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    invoke_signed(
+        &ix,
+        &[from.clone(), mint.clone(), authority.clone(), token_program.clone()],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Create the associated token account of `authority` for `mint` unless it already exists
+pub fn create_associated_token_account<'info>(
+    payer: &AccountInfo<'info>,
+    associated_token: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer.key,
+        authority.key,
+        mint.key,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            associated_token.clone(),
+            authority.clone(),
+            mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+        &[],
+    )
+    .map_err(Into::into)
+}
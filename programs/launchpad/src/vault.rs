@@ -0,0 +1,43 @@
+//! Lamport accounting of the auction vault, which stays rent-exempt while it holds funds
+
+use crate::error::LaunchpadError;
+use anchor_lang::prelude::*;
+
+/// Lamports the vault keeps to stay rent-exempt
+pub fn rent_reserve(vault: &AccountInfo) -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(vault.data_len()))
+}
+
+/// Lamports held beyond the rent reserve and the `owed` tracked totals,
+/// e.g. sent to the vault outside of buys
+pub fn surplus(vault: &AccountInfo, owed: u64) -> Result<u64> {
+    Ok(vault
+        .lamports()
+        .saturating_sub(rent_reserve(vault)?)
+        .saturating_sub(owed))
+}
+
+/// Move `amount` lamports out of the vault, which must stay rent-exempt
+pub fn pay_out<'info>(vault: &AccountInfo<'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    if amount > surplus(vault, 0)? {
+        return Err(LaunchpadError::InsufficientVaultBalance.into());
+    }
+    move_lamports(vault, to, amount)
+}
+
+/// Close the vault once nothing is owed from it, moving all its lamports to `to`
+pub fn close<'info>(vault: &AccountInfo<'info>, to: &AccountInfo<'info>) -> Result<()> {
+    move_lamports(vault, to, vault.lamports())
+}
+
+fn move_lamports<'info>(from: &AccountInfo<'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+    Ok(())
+}
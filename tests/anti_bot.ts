@@ -0,0 +1,178 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("anti_bot", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Seconds after the sale starts before buyers without a whitelist tier can buy
+  const public_tier_delay = 8;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  // Public buyers, each of them can only buy once
+  const buyers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const [early_buyer, first_buyer, second_buyer] = buyers;
+  const buyer_atas = await Promise.all(buyers.map((buyer) => getAssociatedTokenAddress(auction_token, buyer.publicKey)));
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-anti-bot";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+
+  // Sale schedule, set when the auction is created
+  const schedule = {
+    start_time: 0,
+  };
+
+  const buy = (buyer: Keypair) =>
+    program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: buyer.publicKey,
+        buyerAuctionTokenAccount: buyer_atas[buyers.indexOf(buyer)],
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer]);
+
+  const buyerPda = (buyer: Keypair) =>
+    program.account.buyer.fetch(
+      PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+        program.programId
+      )[0]
+    );
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+    const sale_times = saleTimes(start_time, 16, 60, [10, 13]);
+    schedule.start_time = sale_times.startTime.toNumber();
+
+    // Buyers without a whitelist PDA are in the last tier, the only one kept waiting
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...sale_times,
+      tierDelays: [0, 0, 0, public_tier_delay],
+      maxBuysPerSlot: 1,
+    }));
+    console.log("init_auc_tx", tx);
+
+    const add_token_tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", add_token_tx);
+
+    // Fund the buyers and create their token accounts
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        ...buyers.map((buyer) =>
+          SystemProgram.transfer({ fromPubkey: sender.publicKey, toPubkey: buyer.publicKey, lamports: LAMPORTS_PER_SOL / 5 })
+        )
+      ),
+      [sender]
+    );
+    for (const buyer of buyers) {
+      await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, buyer.publicKey);
+    }
+  });
+
+  it("Reject a buy of the public tier before its delay!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay((schedule.start_time + 2) * 1000 - Date.now());
+
+    try {
+      await buy(early_buyer).rpc();
+      assert.fail("the public tier bought before its delay");
+    } catch (err) {
+      assert.include(err.toString(), "TierCooldown");
+    }
+  });
+
+  it("Buy once the delay of the public tier has passed!", async () => {
+    await delay((schedule.start_time + public_tier_delay + 2) * 1000 - Date.now());
+
+    const tx = await buy(early_buyer).rpc();
+    console.log("buy_tx", tx);
+    assert.isTrue((await buyerPda(early_buyer)).participate);
+  });
+
+  it("Reject a second buy in the same slot!", async () => {
+    // Both buys land in the same transaction, so in the same slot
+    try {
+      await buy(first_buyer)
+        .postInstructions([await buy(second_buyer).instruction()])
+        .signers([first_buyer, second_buyer])
+        .rpc();
+      assert.fail("two buys went through in a slot limited to one");
+    } catch (err) {
+      assert.include(err.toString(), "SlotPurchaseLimit");
+    }
+
+    // The first buy was rolled back with the second one, each of them goes through in its own slot
+    console.log("buy_tx", await buy(first_buyer).rpc());
+    console.log("buy_tx", await buy(second_buyer).rpc());
+    assert.isTrue((await buyerPda(first_buyer)).participate);
+    assert.isTrue((await buyerPda(second_buyer)).participate);
+  });
+});
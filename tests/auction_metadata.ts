@@ -0,0 +1,108 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_CLOCK_PUBKEY,
+} from "@solana/web3.js";
+import fs from "fs";
+import { assert } from "chai";
+import { defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("auction metadata", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  );
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-metadata";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, metadata } = pdas;
+
+  const full_metadata = {
+    descriptionUri: "https://arweave.net/lampbit-description",
+    logoUri: "https://arweave.net/lampbit-logo",
+    website: "https://lampbit.io",
+    socialLinks: ["https://x.com/lampbit", "https://t.me/lampbit"],
+    contentHash: Array.from(Buffer.alloc(32, 7)),
+  };
+
+  const setMetadata = (params, signer: Keypair) =>
+    program.methods
+      .setMetadata(params)
+      .accounts({
+        owner: signer.publicKey,
+        auction: auction,
+        metadata: metadata,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 300, 600),
+    }));
+    console.log("init_auc_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.equal(auction_data.name, auction_name);
+    assert.isTrue(auction_data.id.eq(auction_id));
+  });
+
+  it("Set Metadata!", async () => {
+    const tx = await setMetadata(full_metadata, sender);
+    console.log("set_metadata_tx", tx);
+
+    const metadata_data = await program.account.auctionMetadata.fetch(metadata);
+    assert.equal(metadata_data.website, full_metadata.website);
+    assert.deepEqual(metadata_data.socialLinks, full_metadata.socialLinks);
+    assert.deepEqual(metadata_data.contentHash, full_metadata.contentHash);
+  });
+
+  it("Shrink Metadata!", async () => {
+    const before = (await provider.connection.getAccountInfo(metadata)).data.length;
+    await setMetadata({ ...full_metadata, socialLinks: [] }, sender);
+    const after = (await provider.connection.getAccountInfo(metadata)).data.length;
+    assert.isBelow(after, before);
+  });
+
+  it("Reject metadata from anyone but the owner!", async () => {
+    try {
+      await setMetadata(full_metadata, buyer);
+      assert.fail("metadata was set by a non-owner");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+  });
+
+  it("Reject an oversized link!", async () => {
+    try {
+      await setMetadata({ ...full_metadata, website: "https://" + "a".repeat(200) }, sender);
+      assert.fail("oversized website was accepted");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidMetadata");
+    }
+  });
+});
@@ -0,0 +1,201 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("buyer registry", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  );
+
+  const buyer_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, buyer.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-registry";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const [buyer_pda, __] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [whitelist_pda, ___] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [stake_allocation, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const buyer_registry = Keypair.generate();
+  // Entries in a BuyerRegistry
+  const REGISTRY_CAPACITY = 2048;
+
+  const test_data = {
+    token_amount: 360000000,
+    unit_ticket_amount: 400000,
+  };
+
+  const initRegistryAuction = async (auction_pdas, unit_ticket_amount: number) => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    return initAuction(program, sender, auction_pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 10, 120, [4, 7]),
+      tokenQuantityPerTicket: new BN(unit_ticket_amount),
+    }));
+  };
+
+  it("Init Auction!", async () => {
+    const tx = await initRegistryAuction(pdas, test_data.unit_ticket_amount);
+    console.log("init_auc_tx", tx);
+  });
+
+  it("Add Token!", async () => {
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Init Buyer Registry!", async () => {
+    const tx = await program.methods
+      .initBuyerRegistry()
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        buyerRegistry: buyer_registry.publicKey,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .preInstructions([await program.account.buyerRegistry.createInstruction(buyer_registry)])
+      .signers([sender, buyer_registry])
+      .rpc();
+    console.log("init_buyer_registry_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isTrue(auction_data.buyerRegistry.equals(buyer_registry.publicKey));
+  });
+
+  it("Reject a registry smaller than the auction's tickets!", async () => {
+    // Next to the registry's auction, one with more tickets than the registry has entries
+    const large_auction = await nextAuctionPdas(program);
+    const unit_ticket_amount = Math.floor(test_data.token_amount / (REGISTRY_CAPACITY * 2));
+    const tx = await initRegistryAuction(large_auction, unit_ticket_amount);
+    console.log("init_auc_tx", tx);
+
+    const large_registry = Keypair.generate();
+    try {
+      await program.methods
+        .initBuyerRegistry()
+        .accounts({
+          owner: sender.publicKey,
+          auction: large_auction.auction,
+          buyerRegistry: large_registry.publicKey,
+          config: config,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .preInstructions([await program.account.buyerRegistry.createInstruction(large_registry)])
+        .signers([sender, large_registry])
+        .rpc();
+      assert.fail("registry was attached to an auction with more tickets than entries");
+    } catch (err) {
+      assert.include(err.toString(), "BuyerRegistryTooSmall");
+    }
+  });
+
+  it("Register!", async () => {
+    const tx = await program.methods
+      .register()
+      .accounts({
+        buyer: buyer.publicKey,
+        auction: auction,
+        buyerPda: buyer_pda,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("register_tx", tx);
+  });
+
+  it("Buy and get listed in the registry!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(10000);
+
+    const tx = await program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: buyer_pda,
+        referral: buyer.publicKey,
+        buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: whitelist_pda,
+        stakeAllocation: stake_allocation,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: buyer_registry.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("buy_tx", tx);
+
+    const registry = await program.account.buyerRegistry.fetch(buyer_registry.publicKey);
+    assert.equal(registry.count.toNumber(), 1);
+    assert.isTrue(registry.entries[0].buyer.equals(buyer.publicKey));
+    assert.equal(registry.entries[0].tickets.toNumber(), 1);
+
+    const buyer_data = await program.account.buyer.fetch(buyer_pda);
+    assert.equal(registry.entries[0].solPaid.toNumber(), buyer_data.solPaid.toNumber());
+    assert.equal(registry.entries[0].tokenPaid.toNumber(), 0);
+    assert.isTrue(registry.entries[0].paidMint.equals(PublicKey.default));
+    assert.equal(buyer_data.registrySlot, 1);
+  });
+});
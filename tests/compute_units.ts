@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, auctionPdas, defaultAuctionParams, initAuction, saleTimes } from "./utils";
+
+describe("compute units", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Cost of one create_program_address syscall, which find_program_address
+  // repeats from bump 255 down to the canonical bump
+  const CREATE_PROGRAM_ADDRESS_UNITS = 1500;
+  // Ceiling for a public SOL buy, to catch regressions
+  const BUY_UNITS_BUDGET = 60000;
+  // Upcoming auction ids searched for a control and a target auction
+  const CANDIDATE_IDS = 8;
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+
+  const seed = (text: string) => Buffer.from(anchor.utils.bytes.utf8.encode(text));
+
+  // Addresses of the auction created under `id`
+  const auctionAccounts = (id: BN) => {
+    const pdas = auctionPdas(program, id);
+    const [, auction_bump] = PublicKey.findProgramAddressSync(
+      [seed("auction"), id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [, vault_bump] = PublicKey.findProgramAddressSync(
+      [seed("auction_vault"), pdas.auction.toBuffer()],
+      program.programId
+    );
+    return { id, ...pdas, auction_bump, vault_bump };
+  };
+
+  // Before the bumps were stored, a buy searched 256 - bump addresses for the auction,
+  // and for the vault twice (its constraint and the signer seeds)
+  const searches = (bump: number) => 256 - bump;
+  const derivations = ({ auction_bump, vault_bump }) =>
+    searches(auction_bump) + 2 * searches(vault_bump);
+
+  // A buyer whose own PDAs have bump 255, so that they cost the same under every auction
+  const cheapBuyer = (auction: PublicKey) => {
+    for (;;) {
+      const buyer = Keypair.generate();
+      const bumps = [
+        [seed("buyer"), buyer.publicKey.toBuffer(), auction.toBuffer()],
+        [seed("whitelist"), buyer.publicKey.toBuffer(), auction.toBuffer()],
+        [seed("stake_allocation"), auction.toBuffer(), buyer.publicKey.toBuffer()],
+      ].map((seeds) => PublicKey.findProgramAddressSync(seeds, program.programId)[1]);
+      if (bumps.every((bump) => bump == 255)) {
+        return buyer;
+      }
+    }
+  };
+
+  const unitsConsumed = async (tx: string) => {
+    const info = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return info.meta.computeUnitsConsumed;
+  };
+
+  const initCandidate = async (candidate) => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    return initAuction(program, sender, candidate, defaultAuctionParams({
+      // Same length for every auction, so that they deserialize at the same cost
+      name: "lampbit-auction-cu",
+      ...saleTimes(start_time, 10, 300, [4, 7]),
+    }));
+  };
+
+  // Fund the buyer, create its auction token account and register it
+  const prepareBuyer = async ({ auction }, buyer: Keypair) => {
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: buyer.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, buyer.publicKey);
+
+    await program.methods
+      .register()
+      .accounts({
+        buyer: buyer.publicKey,
+        auction: auction,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [seed("buyer"), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+  };
+
+  const buy = async ({ auction, auction_vault }, buyer: Keypair) => {
+    const tx = await program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [seed("buyer"), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: buyer.publicKey,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, buyer.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [seed("whitelist"), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [seed("stake_allocation"), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc({ commitment: "confirmed" });
+    console.log("buy_tx", tx);
+    return unitsConsumed(tx);
+  };
+
+  // The control auction is the cheapest one to derive, bumps 255, the target the most
+  // expensive among the next ids; every other cost of their buys is the same
+  const next_id = (await program.account.config.fetch(config)).auctionCount;
+  const candidates = [...Array(CANDIDATE_IDS).keys()].map((i) => auctionAccounts(next_id.addn(i)));
+  const control = candidates.find((candidate) => derivations(candidate) == 3);
+  const target = candidates.reduce((worst, candidate) =>
+    derivations(candidate) > derivations(worst) ? candidate : worst
+  );
+  const control_buyer = control && cheapBuyer(control.auction);
+  const target_buyer = cheapBuyer(target.auction);
+
+  it("Init the control and target auctions!", async () => {
+    assert.isDefined(control, "no auction with bumps 255 among the next ids");
+    assert.isAbove(derivations(target), derivations(control));
+
+    // Ids are assigned in order, so the ones in between are created too
+    const last_id = BN.max(control.id, target.id);
+    for (const candidate of candidates.filter((candidate) => candidate.id.lte(last_id))) {
+      const tx = await initCandidate(candidate);
+      console.log("init_auc_tx", candidate.id.toString(), tx);
+
+      const auction_data = await program.account.auction.fetch(candidate.auction);
+      assert.equal(auction_data.bump, candidate.auction_bump);
+      assert.equal(auction_data.vaultBump, candidate.vault_bump);
+
+      if (candidate == control || candidate == target) {
+        console.log("add_token_tx", await addToken(program, sender, candidate, auction_token));
+      }
+    }
+
+    await prepareBuyer(control, control_buyer);
+    await prepareBuyer(target, target_buyer);
+  });
+
+  it("Buy at the same cost whatever the bumps!", async () => {
+    console.log("Lets wait for the Auctions to go LIVE...");
+    await delay(12000);
+
+    const control_units = await buy(control, control_buyer);
+    const target_units = await buy(target, target_buyer);
+
+    // Deriving the bumps would have cost the target this much more than the control
+    const derivation_units = (derivations(target) - derivations(control)) * CREATE_PROGRAM_ADDRESS_UNITS;
+    console.log(
+      "buy compute units, control:", control_units,
+      "target:", target_units,
+      "derivation units avoided:", derivation_units,
+    );
+
+    // With the stored bumps the target costs no more than the control, within one derivation
+    assert.isBelow(Math.abs(target_units - control_units), CREATE_PROGRAM_ADDRESS_UNITS);
+    assert.isAtMost(target_units, BUY_UNITS_BUDGET);
+  });
+});
@@ -0,0 +1,383 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("escrow", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Seconds buyers have to vote on a milestone
+  const vote_period = 6;
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner, and the beneficiary of the escrows
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  );
+  const voter = Keypair.generate(); // A second buyer of the voted auction
+  const approver = Keypair.generate(); // Approves the milestones of the approved auction
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+
+  const seed = (text: string) => Buffer.from(anchor.utils.bytes.utf8.encode(text));
+
+  // The auctions init_auction creates next, the approved one first
+  const auctionAccounts = async (offset: number) => {
+    const pdas = await nextAuctionPdas(program, offset);
+    const [escrow] = PublicKey.findProgramAddressSync([seed("escrow"), pdas.auction.toBuffer()], program.programId);
+    return { ...pdas, escrow };
+  };
+  const approved = await auctionAccounts(0);
+  const voted = await auctionAccounts(1);
+
+  const buyerPda = ({ auction }, wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync([seed("buyer"), wallet.toBuffer(), auction.toBuffer()], program.programId)[0];
+
+  const initEscrowedAuction = async (pdas, start_time: number, name: string) =>
+    initAuction(program, sender, pdas, defaultAuctionParams({
+      name,
+      ...saleTimes(start_time, 20, 45, [14, 17]),
+    }));
+
+  // Two milestones releasing half of the escrow each
+  const initEscrow = async ({ auction, escrow }, escrow_approver: PublicKey) =>
+    program.methods
+      .initEscrow({
+        approver: escrow_approver,
+        votePeriod: new BN(vote_period),
+        milestoneCount: 2,
+        trancheBps: [5000, 5000, 0, 0],
+      })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        escrow: escrow,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+  const buy = async ({ auction, auction_vault }, wallet: Keypair) =>
+    program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: wallet.publicKey,
+        buyerPda: buyerPda({ auction }, wallet.publicKey),
+        referral: wallet.publicKey,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, wallet.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [seed("whitelist"), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [seed("stake_allocation"), auction.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const withdrawFunds = async ({ auction, auction_vault, escrow }) =>
+    program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [seed("delegate"), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: escrow,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+  const requestMilestone = async ({ escrow }) =>
+    program.methods
+      .requestMilestone()
+      .accounts({
+        beneficiary: sender.publicKey,
+        escrow: escrow,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([sender])
+      .rpc();
+
+  const approveMilestone = async ({ auction, auction_vault, escrow }, approve: boolean) =>
+    program.methods
+      .approveMilestone(approve)
+      .accounts({
+        approver: approver.publicKey,
+        auction: auction,
+        escrow: escrow,
+        auctionVault: auction_vault,
+        beneficiary: sender.publicKey,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+      })
+      .signers([approver])
+      .rpc();
+
+  const voteMilestone = async ({ auction, escrow }, wallet: Keypair, approve: boolean) => {
+    const current_milestone = (await program.account.escrow.fetch(escrow)).currentMilestone;
+    return program.methods
+      .voteMilestone(approve)
+      .accounts({
+        buyer: wallet.publicKey,
+        escrow: escrow,
+        buyerPda: buyerPda({ auction }, wallet.publicKey),
+        milestoneVote: PublicKey.findProgramAddressSync(
+          [seed("milestone_vote"), auction.toBuffer(), wallet.publicKey.toBuffer(), Buffer.from([current_milestone])],
+          program.programId
+        )[0],
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  };
+
+  // Anyone can resolve a vote once it is over, here the provider wallet
+  const resolveMilestoneVote = async ({ auction, auction_vault, escrow }) =>
+    program.methods
+      .resolveMilestoneVote()
+      .accounts({
+        auction: auction,
+        escrow: escrow,
+        auctionVault: auction_vault,
+        beneficiary: sender.publicKey,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+
+  const claimEscrowRefund = async ({ auction, auction_vault, escrow }, wallet: Keypair) =>
+    program.methods
+      .claimEscrowRefund()
+      .accounts({
+        buyer: wallet.publicKey,
+        escrow: escrow,
+        buyerPda: buyerPda({ auction }, wallet.publicKey),
+        auctionVault: auction_vault,
+        config: config,
+      })
+      .signers([wallet])
+      .rpc();
+
+  // The beneficiary gets the tranche less the auction's fee, the fee payer of every
+  // transaction is the provider wallet so the balances move by these amounts only
+  const trancheAfterFee = async ({ auction, escrow }) => {
+    const fee_bps = (await program.account.auction.fetch(auction)).feeBps;
+    const escrow_data = await program.account.escrow.fetch(escrow);
+    const tranche = Math.floor((escrow_data.solTotal.toNumber() * 5000) / 10000);
+    return tranche - Math.floor((tranche * fee_bps) / 10000);
+  };
+
+  // What a rejected escrow owes a buyer, pro rata to what they paid
+  const expectedRefund = async ({ auction, escrow }, wallet: PublicKey) => {
+    const escrow_data = await program.account.escrow.fetch(escrow);
+    const buyer_data = await program.account.buyer.fetch(buyerPda({ auction }, wallet));
+    const remaining = escrow_data.solTotal.sub(escrow_data.solReleased);
+    return remaining.mul(buyer_data.solPaid).div(escrow_data.solPaidTotal).toNumber();
+  };
+
+  it("Init the escrowed auctions!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    console.log("init_auc_tx", await initEscrowedAuction(approved, start_time, "lampbit-escrow-approved"));
+    console.log("init_auc_tx", await initEscrowedAuction(voted, start_time, "lampbit-escrow-voted"));
+    console.log("add_token_tx", await addToken(program, sender, approved, auction_token));
+    console.log("add_token_tx", await addToken(program, sender, voted, auction_token));
+    console.log("init_escrow_tx", await initEscrow(approved, approver.publicKey));
+    console.log("init_escrow_tx", await initEscrow(voted, PublicKey.default));
+
+    // Fund the second buyer and create its auction token account
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: voter.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, voter.publicKey);
+  });
+
+  it("Buy and escrow the proceeds!", async () => {
+    console.log("Lets wait for the Auctions to go LIVE...");
+    await delay(22000);
+
+    console.log("buy_tx", await buy(approved, buyer));
+    console.log("buy_tx", await buy(voted, buyer));
+    console.log("buy_tx", await buy(voted, voter));
+
+    console.log("Waiting for the Auctions to End...");
+    await delay(25000);
+
+    for (const accounts of [approved, voted]) {
+      const auction_data = await program.account.auction.fetch(accounts.auction);
+      const tx = await withdrawFunds(accounts);
+      console.log("withdraw_funds_tx", tx);
+
+      // The SOL stays in the vault, escrowed for the owner
+      const escrow_data = await program.account.escrow.fetch(accounts.escrow);
+      assert.isTrue(escrow_data.funded);
+      assert.isTrue(escrow_data.beneficiary.equals(sender.publicKey));
+      assert.equal(escrow_data.solTotal.toNumber(), auction_data.solCollected.toNumber());
+      assert.equal(escrow_data.solPaidTotal.toNumber(), auction_data.solPaidTotal.toNumber());
+    }
+  });
+
+  it("Approve a milestone!", async () => {
+    console.log("request_milestone_tx", await requestMilestone(approved));
+
+    const expected = await trancheAfterFee(approved);
+    const before = await provider.connection.getBalance(sender.publicKey);
+    const tx = await approveMilestone(approved, true);
+    console.log("approve_milestone_tx", tx);
+
+    assert.equal((await provider.connection.getBalance(sender.publicKey)) - before, expected);
+    const escrow_data = await program.account.escrow.fetch(approved.escrow);
+    assert.equal(escrow_data.currentMilestone, 1);
+    assert.isFalse(escrow_data.rejected);
+  });
+
+  it("Reject a milestone and refund the buyer!", async () => {
+    console.log("request_milestone_tx", await requestMilestone(approved));
+    console.log("approve_milestone_tx", await approveMilestone(approved, false));
+
+    const escrow_data = await program.account.escrow.fetch(approved.escrow);
+    assert.isTrue(escrow_data.rejected);
+
+    // The only buyer gets back everything that was not released
+    const expected = await expectedRefund(approved, buyer.publicKey);
+    assert.equal(expected, escrow_data.solTotal.sub(escrow_data.solReleased).toNumber());
+    const before = await provider.connection.getBalance(buyer.publicKey);
+    console.log("claim_escrow_refund_tx", await claimEscrowRefund(approved, buyer));
+    assert.equal((await provider.connection.getBalance(buyer.publicKey)) - before, expected);
+
+    // And only once
+    try {
+      await claimEscrowRefund(approved, buyer);
+      assert.fail("escrow refund was claimed twice");
+    } catch (err) {
+      assert.include(err.toString(), "NothingToClaim");
+    }
+  });
+
+  it("Pass a milestone by buyer vote!", async () => {
+    console.log("request_milestone_tx", await requestMilestone(voted));
+    console.log("vote_milestone_tx", await voteMilestone(voted, buyer, true));
+    console.log("vote_milestone_tx", await voteMilestone(voted, voter, false));
+
+    // Resolving before the vote is over is rejected
+    try {
+      await resolveMilestoneVote(voted);
+      assert.fail("milestone vote was resolved while open");
+    } catch (err) {
+      assert.include(err.toString(), "VoteNotEnded");
+    }
+
+    await delay((vote_period + 2) * 1000);
+
+    // A tie passes the milestone
+    const expected = await trancheAfterFee(voted);
+    const before = await provider.connection.getBalance(sender.publicKey);
+    console.log("resolve_milestone_vote_tx", await resolveMilestoneVote(voted));
+    assert.equal((await provider.connection.getBalance(sender.publicKey)) - before, expected);
+
+    const escrow_data = await program.account.escrow.fetch(voted.escrow);
+    assert.equal(escrow_data.currentMilestone, 1);
+    assert.isFalse(escrow_data.rejected);
+  });
+
+  it("Reject a milestone by buyer vote and refund pro rata!", async () => {
+    console.log("request_milestone_tx", await requestMilestone(voted));
+    console.log("vote_milestone_tx", await voteMilestone(voted, buyer, false));
+    await delay((vote_period + 2) * 1000);
+
+    console.log("resolve_milestone_vote_tx", await resolveMilestoneVote(voted));
+    const escrow_data = await program.account.escrow.fetch(voted.escrow);
+    assert.isTrue(escrow_data.rejected);
+
+    // Both buyers paid one ticket, so each gets back half of what is left
+    const remaining = escrow_data.solTotal.sub(escrow_data.solReleased).toNumber();
+    for (const wallet of [buyer, voter]) {
+      const expected = await expectedRefund(voted, wallet.publicKey);
+      assert.equal(expected, Math.floor(remaining / 2));
+
+      const before = await provider.connection.getBalance(wallet.publicKey);
+      console.log("claim_escrow_refund_tx", await claimEscrowRefund(voted, wallet));
+      assert.equal((await provider.connection.getBalance(wallet.publicKey)) - before, expected);
+    }
+  });
+});
@@ -0,0 +1,277 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("finalize auction", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Seconds the creator has to withdraw after the end before anyone can finalize
+  const grace_period = 30;
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Token accepted as payment alongside SOL
+  const bid_token = new PublicKey("6YMTJpgraqrd68mBfjkwG65FPuHiZWuifi4UP1WUoHjK");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  );
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+  const buyer_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, buyer.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-finalize";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const auction_vault_bidtoken_ata = await getAssociatedTokenAddress(bid_token, auction_vault, true);
+  const [payment_option, _______] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("payment_option")), auction.toBuffer(), bid_token.toBuffer()],
+    program.programId
+  );
+  const [buyer_pda, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [whitelist_pda, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [stake_allocation, ______] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+    program.programId
+  );
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 10, 30, [6, 8]),
+      gracePeriod: new BN(grace_period),
+    }));
+    console.log("init_auc_tx", tx);
+
+    // Destinations left as default go to the owner
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isTrue(auction_data.proceedsDestination.equals(sender.publicKey));
+    assert.isTrue(auction_data.unsoldDestination.equals(sender.publicKey));
+
+    // The platform fee is fixed at init, later config changes don't apply to it
+    assert.equal(auction_data.feeBps, (await program.account.config.fetch(config)).feeBps);
+  });
+
+  it("Add Token!", async () => {
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Add Payment Option!", async () => {
+    const tx = await program.methods
+      .addPaymentOption({ pricePerTicket: new BN(1000000) })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        config: config,
+        paymentOption: payment_option,
+        auctionVaultBidTokenAccount: auction_vault_bidtoken_ata,
+        bidToken: bid_token,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("add_payment_option_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.equal(auction_data.paymentOptionCount, 1);
+  });
+
+  it("Register!", async () => {
+    const tx = await program.methods
+      .register()
+      .accounts({
+        buyer: buyer.publicKey,
+        auction: auction,
+        buyerPda: buyer_pda,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("register_tx", tx);
+  });
+
+  it("Buy!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(10000);
+
+    const tx = await program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: buyer_pda,
+        referral: buyer.publicKey,
+        buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: whitelist_pda,
+        stakeAllocation: stake_allocation,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("buy_tx", tx);
+  });
+
+  // Bid token accounts of the vault, the proceeds destination and the treasury for the payment option
+  const paymentAccounts = async () => {
+    const fee_treasury = (await program.account.config.fetch(config)).feeTreasury;
+    const destination_bidtoken_ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection, sender, bid_token, sender.publicKey
+    );
+    const treasury_bidtoken_ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection, sender, bid_token, fee_treasury
+    );
+    return [
+      payment_option,
+      auction_vault_bidtoken_ata,
+      destination_bidtoken_ata.address,
+      treasury_bidtoken_ata.address,
+      bid_token,
+      TOKEN_PROGRAM_ID,
+    ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: !pubkey.equals(TOKEN_PROGRAM_ID) }));
+  };
+
+  // Anyone can send it, here the provider wallet rather than the owner
+  const finalizeAuction = async (payment_accounts) =>
+    program.methods
+      .finalizeAuction()
+      .accounts({
+        auction: auction,
+        auctionVault: auction_vault,
+        proceedsDestination: sender.publicKey,
+        auctionVaultTokenAccount: auction_vault_ata,
+        unsoldTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(payment_accounts)
+      .rpc();
+
+  it("Reject finalizing during the grace period!", async () => {
+    console.log("Lets wait for Auction to end...");
+    await delay(25000);
+
+    try {
+      await finalizeAuction(await paymentAccounts());
+      assert.fail("auction was finalized during the grace period");
+    } catch (err) {
+      assert.include(err.toString(), "GracePeriodNotOver");
+    }
+  });
+
+  it("Reject finalizing without the payment accounts!", async () => {
+    console.log("Lets wait for the grace period to pass...");
+    await delay(grace_period * 1000);
+
+    // The bid token proceeds would be stranded once the auction is marked finalized
+    try {
+      await finalizeAuction([]);
+      assert.fail("auction was finalized without its payment option");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPaymentAccounts");
+    }
+
+    // Nor with the payment option passed more than once
+    const payment_accounts = await paymentAccounts();
+    try {
+      await finalizeAuction([...payment_accounts, ...payment_accounts]);
+      assert.fail("auction was finalized with a payment option passed twice");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPaymentAccounts");
+    }
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.equal(auction_data.finalizedAt.toNumber(), 0);
+  });
+
+  it("Finalize Auction!", async () => {
+    const before = await provider.connection.getBalance(sender.publicKey);
+    const tx = await finalizeAuction(await paymentAccounts());
+    console.log("finalize_auction_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isTrue(auction_data.finalizedAt.gtn(0));
+    assert.isAbove(await provider.connection.getBalance(sender.publicKey), before);
+  });
+
+  it("Reject finalizing twice!", async () => {
+    try {
+      await finalizeAuction(await paymentAccounts());
+      assert.fail("auction was finalized twice");
+    } catch (err) {
+      assert.include(err.toString(), "AuctionFinalized");
+    }
+  });
+});
@@ -0,0 +1,161 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Ed25519Program,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("kyc attestation", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  );
+  const kyc_authority = Keypair.generate();
+
+  const buyer_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, buyer.publicKey);
+
+  const [config, ___] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-kyc";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const [buyer_pda, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [whitelist_pda, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+
+  // buyer (32) | auction (32) | expiry (i64 LE) | tier (u8)
+  const attestation = (expiry: number, tier: number) => {
+    const message = Buffer.alloc(73);
+    buyer.publicKey.toBuffer().copy(message, 0);
+    auction.toBuffer().copy(message, 32);
+    message.writeBigInt64LE(BigInt(expiry), 64);
+    message.writeUInt8(tier, 72);
+    return Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: kyc_authority.secretKey,
+      message,
+    });
+  };
+
+  const preSaleBuy = (attestation_ix) =>
+    program.methods
+      .buy({ preSale: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: buyer_pda,
+        referral: buyer.publicKey,
+        buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: whitelist_pda,
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([attestation_ix])
+      .signers([buyer]);
+
+  it("Init KYC Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 30, 60, [4, 25]),
+      kycAuthority: kyc_authority.publicKey,
+    }));
+    console.log("init_auc_tx", tx);
+  });
+
+  it("Add Token!", async () => {
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Register!", async () => {
+    const tx = await program.methods
+      .register()
+      .accounts({
+        buyer: buyer.publicKey,
+        auction: auction,
+        buyerPda: buyer_pda,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("register_tx", tx);
+  });
+
+  it("Reject an expired attestation!", async () => {
+    console.log("Lets wait for PreSale to go LIVE...");
+    await delay(5000);
+
+    const expired = Math.floor(Date.now() / 1000) - 60;
+    try {
+      await preSaleBuy(attestation(expired, 0)).rpc();
+      assert.fail("expired attestation was accepted");
+    } catch (err) {
+      assert.include(err.toString(), "AttestationExpired");
+    }
+  });
+
+  it("PreSale Buy with a KYC attestation!", async () => {
+    const expiry = Math.floor(Date.now() / 1000) + 600;
+    const tx = await preSaleBuy(attestation(expiry, 0)).rpc();
+    console.log("presale_buy_tx", tx);
+
+    const buyer_data = await program.account.buyer.fetch(buyer_pda);
+    assert.isTrue(buyer_data.participate);
+  });
+});
@@ -7,21 +7,24 @@ import {
   Keypair,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
-  LAMPORTS_PER_SOL,
   SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import {
-  getAssociatedTokenAddress, 
-  TOKEN_PROGRAM_ID, 
+  getAccount,
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
-  createAssociatedTokenAccountInstruction
 } from "@solana/spl-token";
 import fs from "fs";
 import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
 
 describe("anchor-latest", async () => {
   // Configure the client to use the devnet cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.Launchpad as Program<Launchpad>;
   console.log("programId:", program.programId.toString());
@@ -44,12 +47,6 @@ describe("anchor-latest", async () => {
   );
   console.log("sender_auctiontoken_ata", sender_auctiontoken_ata.toString())
 
-  const sender_bidtoken_ata = await getAssociatedTokenAddress(
-    bid_token,
-    sender.publicKey,
-  );
-  console.log("sender_bidtoken_ata", sender_bidtoken_ata.toString())
-
   const buyer = Keypair.fromSecretKey(
     Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
   );  // This is the buyer who buys/bids auction token
@@ -58,37 +55,38 @@ describe("anchor-latest", async () => {
   const buyer_bidtoken_ata = new PublicKey("5Q3NSjAYBFNyWL6sJkiz7YidYpBqgxrYTK5nnrfkzFcR");
   console.log("buyer_bidtoken_ata:", buyer_bidtoken_ata.toString());
 
-  // NOTE: Initially, buyer doesn't have buyer_auction_token_account.
-  // We can check and create buyer_auction_token_account in SC if it doesn't exists.
   const buyer_auctiontoken_ata = await getAssociatedTokenAddress(
     auction_token,
     buyer.publicKey,
   );
   console.log("buyer_auctiontoken_ata", buyer_auctiontoken_ata.toString());
 
-  const auction_pda_name = "lampbit-auction-contract";
-  const [auction, _] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(anchor.utils.bytes.utf8.encode("auction")),
-      Buffer.from(anchor.utils.bytes.utf8.encode(auction_pda_name)),
-    ],
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
     program.programId
   );
+  const auction_name = "lampbit-auction-contract";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
   console.log("auction:", auction.toString());
 
-  const auction_vault_ata = await getAssociatedTokenAddress(
-    auction_token,
-    auction,
-    true
-  );
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
   console.log("auction_vault_ata", auction_vault_ata.toString())
+  const auction_bidtoken_ata = await getAssociatedTokenAddress(bid_token, auction_vault, true);
+  console.log("auction_bidtoken_ata", auction_bidtoken_ata.toString())
 
-  const auction_bidtoken_ata = await getAssociatedTokenAddress(
-    bid_token,
-    auction,
-    true
+  const [payment_option, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("payment_option")), auction.toBuffer(), bid_token.toBuffer()],
+    program.programId
   );
-  console.log("auction_bidtoken_ata", auction_bidtoken_ata.toString())
+  const [buyer_pda, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+
+  // Price of a ticket in bid token base units
+  const bid_price_per_ticket = 1000000;
 
   // a function to set timeout or sleep
   const delay = ms => new Promise(res => setTimeout(res, ms));
@@ -98,93 +96,156 @@ describe("anchor-latest", async () => {
     const start_time = Math.floor(Date.now() / 1000);
     console.log("start_time:", start_time);
 
-    const unit_price = 1;
-    const tokenCap = 2;
-
-    const tx = await program.methods
-      .initAuction({
-        name: auction_pda_name,
-        enabled: true,
-        fixedAmount: true,
-        startTime: new BN(start_time),
-        endTime: new BN(start_time + 7),
-        unitPrice: new BN(unit_price),
-        tokenCap: new BN(tokenCap * LAMPORTS_PER_SOL),
-        payWithNative: false,
-      })
-      .accounts({
-        owner: sender.publicKey,
-        auction: auction,
-        rent: SYSVAR_RENT_PUBKEY,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([sender])
-      .rpc();
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 10, 17, [6, 8]),
+      // Paid in the bid token only
+      payWithNative: false,
+      gracePeriod: new BN(60),
+    }));
     console.log("Your transaction signature", tx);
   });
 
   it("Add Token!", async () => {
-    const tx = await program.methods.addToken()
+    const tx = await addToken(program, sender, pdas, auction_token);
+
+    console.log("Your transaction signature", tx);
+  });
+
+  it("Add Payment Option!", async () => {
+    const tx = await program.methods.addPaymentOption({
+      pricePerTicket: new BN(bid_price_per_ticket),
+    })
     .accounts({
       owner: sender.publicKey,
       auction: auction,
-      ownerAuctionTokenAccount: sender_auctiontoken_ata,
-      auctionVaultTokenAccount: auction_vault_ata,
-      auctionToken: auction_token,
+      auctionVault: auction_vault,
+      config: config,
+      paymentOption: payment_option,
+      auctionVaultBidTokenAccount: auction_bidtoken_ata,
+      bidToken: bid_token,
       rent: SYSVAR_RENT_PUBKEY,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      clock: SYSVAR_CLOCK_PUBKEY,
     }).signers([sender])
     .rpc();
 
     console.log("Your transaction signature", tx);
   });
 
+  it("Register!", async () => {
+    const tx = await program.methods.register()
+    .accounts({
+      buyer: buyer.publicKey,
+      auction: auction,
+      buyerPda: buyer_pda,
+      config: config,
+      clock: SYSVAR_CLOCK_PUBKEY,
+      systemProgram: SystemProgram.programId,
+    }).signers([buyer])
+    .rpc();
+
+    console.log("Your transaction signature", tx);
+  });
+
   it("Buy Tokens using SPL!", async () => {
-    const bidding_spl_amount = 1;
-
-    const tx = await program.methods.buyTokenUsingSpl(
-      new BN(bidding_spl_amount * LAMPORTS_PER_SOL)
-      ).accounts({
-        buyer: buyer.publicKey,
-        buyerBidTokenAccount: buyer_bidtoken_ata,
-        buyerAuctionTokenAccount: buyer_auctiontoken_ata,
-        auction: auction,
-        auctionVaultTokenAccount: auction_vault_ata,
-        auctionVaultBidTokenAccount: auction_bidtoken_ata,
-        auctionToken: auction_token,
-        bidToken: bid_token,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        clock: SYSVAR_CLOCK_PUBKEY,
-      }).signers([buyer])
-      .rpc();
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(10000);
+
+    const tx = await program.methods.buy({ public: {} }, null)
+    .accounts({
+      buyer: buyer.publicKey,
+      buyerPda: buyer_pda,
+      referral: buyer.publicKey,
+      buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+      auction: auction,
+      config: config,
+      auctionVault: auction_vault,
+      auctionVaultTokenAccount: auction_vault_ata,
+      auctionToken: auction_token,
+      whitelistPda: PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+        program.programId
+      )[0],
+      stakeAllocation: PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+        program.programId
+      )[0],
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      priceFeed: PublicKey.default,
+      paymentOption: payment_option,
+      bidToken: bid_token,
+      buyerBidTokenAccount: buyer_bidtoken_ata,
+      auctionVaultBidTokenAccount: auction_bidtoken_ata,
+      bidTokenProgram: TOKEN_PROGRAM_ID,
+      buyerRegistry: null,
+      clock: SYSVAR_CLOCK_PUBKEY,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    }).signers([buyer])
+    .rpc();
+
+    console.log("Your transaction signature", tx);
 
-      console.log("Your transaction signature", tx);
+    const payment_option_data = await program.account.paymentOption.fetch(payment_option);
+    assert.equal(payment_option_data.totalCollected.toNumber(), bid_price_per_ticket);
   });
 
   it("Withdraw Funds!", async () => {
-    console.log("Waiting for 7 secs...")
+    console.log("Lets wait for Auction to end...");
     await delay(8000);
-    console.log("7 secs Over")
+
+    const fee_treasury = (await program.account.config.fetch(config)).feeTreasury;
+    const sender_bidtoken_ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection, sender, bid_token, sender.publicKey
+    );
+    const treasury_bidtoken_ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection, sender, bid_token, fee_treasury
+    );
+    const before = (await getAccount(provider.connection, sender_bidtoken_ata.address)).amount;
 
     const tx = await program.methods.withdrawFunds()
     .accounts({
         creator: sender.publicKey,
         auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
         auctionVaultTokenAccount: auction_vault_ata,
-        auctionVaultBidTokenAccount: auction_bidtoken_ata,
         creatorAuctionTokenAccount: sender_auctiontoken_ata,
-        creatorBidTokenAccount: sender_bidtoken_ata,
         auctionToken: auction_token,
-        bidToken: bid_token,
+        config: config,
+        feeTreasury: fee_treasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
         tokenProgram: TOKEN_PROGRAM_ID,
         clock: SYSVAR_CLOCK_PUBKEY,
-      }).signers([sender])
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: payment_option, isSigner: false, isWritable: true },
+        { pubkey: auction_bidtoken_ata, isSigner: false, isWritable: true },
+        { pubkey: sender_bidtoken_ata.address, isSigner: false, isWritable: true },
+        { pubkey: treasury_bidtoken_ata.address, isSigner: false, isWritable: true },
+        { pubkey: bid_token, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ])
+      .signers([sender])
       .rpc();
 
-      console.log("Your transaction signature", tx);
+    console.log("Your transaction signature", tx);
+
+    // The owner got the bid token proceeds, less the platform fee
+    const after = (await getAccount(provider.connection, sender_bidtoken_ata.address)).amount;
+    assert.isTrue(after > before);
+    assert.isTrue(after - before <= BigInt(bid_price_per_ticket));
   });
 
 });
@@ -9,14 +9,14 @@ import {
   SYSVAR_RENT_PUBKEY,
   LAMPORTS_PER_SOL,
   SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   Connection
 } from "@solana/web3.js";
 import {
   getAssociatedTokenAddress, 
   TOKEN_PROGRAM_ID, 
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { createATA } from "./utils";
+import { addToken, createATA, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
 import fs from "fs";
 import { assert } from "chai";
 
@@ -70,58 +70,65 @@ describe("anchor-latest", async () => {
   );
   console.log("buyer_auctiontoken_ata", buyer_auctiontoken_ata.toString());
 
-  const auction_pda_name = "lampbit-auction-edge2";
-  const [auction, _] = PublicKey.findProgramAddressSync(
+  const [config, _____] = PublicKey.findProgramAddressSync(
     [
-      Buffer.from(anchor.utils.bytes.utf8.encode("auction")),
-      Buffer.from(anchor.utils.bytes.utf8.encode(auction_pda_name)),
-    ],
-    program.programId
-  );
-  console.log("auction:", auction.toString());
-
-  const [buyer_pda, ____] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(anchor.utils.bytes.utf8.encode("buyer")),
-      buyer.publicKey.toBuffer(),
-      auction.toBuffer()
+      Buffer.from(anchor.utils.bytes.utf8.encode("config")),
     ],
     program.programId
   )
-  console.log("buyer_pda:", buyer_pda.toString());
-
-  const [auction_vault, __] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(anchor.utils.bytes.utf8.encode("auction_vault")),
-      auction.toBuffer()
-    ],
-    program.programId
-  )
-  console.log("auction_vault:", auction_vault.toString());
-
-  const auction_vault_ata = await getAssociatedTokenAddress(
-    auction_token,
-    auction_vault,
-    true
-  );
-  console.log("auction_vault_ata", auction_vault_ata.toString())
-
-  const auction_vault_bidtoken_ata = await getAssociatedTokenAddress(
-    bid_token,
-    auction_vault,
-    true
-  );
-  console.log("auction_vault_bidtoken_ata", auction_vault_bidtoken_ata.toString())
+  console.log("config:", config.toString());
+
+  const auction_name = "lampbit-auction-edge2";
+  // Each case creates a new auction, under the next id assigned by init_auction
+  let pdas;
+  let auction: PublicKey;
+  let buyer_pda: PublicKey;
+  let auction_vault: PublicKey;
+  let auction_vault_ata: PublicKey;
+  let auction_vault_bidtoken_ata: PublicKey;
+  let whitelist_pda: PublicKey;
+  const useNextAuction = async () => {
+    pdas = await nextAuctionPdas(program);
+    ({ auction, auction_vault } = pdas);
+    console.log("auction:", auction.toString());
+
+    [buyer_pda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(anchor.utils.bytes.utf8.encode("buyer")),
+        buyer.publicKey.toBuffer(),
+        auction.toBuffer()
+      ],
+      program.programId
+    )
+    console.log("buyer_pda:", buyer_pda.toString());
+
+    console.log("auction_vault:", auction_vault.toString());
+
+    auction_vault_ata = await getAssociatedTokenAddress(
+      auction_token,
+      auction_vault,
+      true
+    );
+    console.log("auction_vault_ata", auction_vault_ata.toString())
 
-  const [whitelist_pda, ___] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")),
-      buyer.publicKey.toBuffer(),
-      auction.toBuffer(),
-    ],
-    program.programId
-  );
-  console.log("whitelist_pda:", whitelist_pda.toString());
+    auction_vault_bidtoken_ata = await getAssociatedTokenAddress(
+      bid_token,
+      auction_vault,
+      true
+    );
+    console.log("auction_vault_bidtoken_ata", auction_vault_bidtoken_ata.toString())
+
+    [whitelist_pda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")),
+        buyer.publicKey.toBuffer(),
+        auction.toBuffer(),
+      ],
+      program.programId
+    );
+    console.log("whitelist_pda:", whitelist_pda.toString());
+  };
+  await useNextAuction();
 
   // Reference Data
   const actual_data = {
@@ -157,6 +164,35 @@ describe("anchor-latest", async () => {
   // console.log("remainingTokens", auction_data.remainingTokens.toNumber())
   // assert(false)
 
+  describe("Config SanityCheck!", async () => {
+    it("Check whether the config exists or not!", async () => {
+      if (! await con.getAccountInfo(config)) {
+        console.log("Config doesn't exist!! Creating one...")
+        // The config can only be created by the program upgrade authority
+        const [program_data, _______] = PublicKey.findProgramAddressSync(
+          [program.programId.toBuffer()],
+          new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+        )
+        const init_config_tx = await program.methods.initializeConfig({
+            feeBps: 100,
+            feeTreasury: provider.wallet.publicKey,
+            listingFee: new BN(0),
+          })
+          .accounts({
+            admin: provider.wallet.publicKey,
+            config: config,
+            program: program.programId,
+            programData: program_data,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        console.log("init_config_tx", init_config_tx)
+      } else {
+        console.log(config.toString(), "exists!")
+      }
+    })
+  });
+
   describe("ATA SanityCheck!", async () => {
     it("Check whether all required ATAs exists or not!", async () => {
       if (! await con.getAccountInfo(sender_auctiontoken_ata)) {
@@ -207,30 +243,11 @@ describe("anchor-latest", async () => {
         const start_time = Math.floor(Date.now() / 1000);
         console.log("start_time:", start_time);
     
-        const init_auc_tx = await program.methods
-          .initAuction({
-            name: auction_pda_name,
-            enabled: true,
-            fixedAmount: true,
-            startTime: new BN(start_time + 15),
-            endTime: new BN(start_time + 25),
-            payWithNative: true,
-            preSale: true,
-            preSaleStartTime: new BN(start_time),
-            preSaleEndTime: new BN(start_time + 10),
-            tokensInPool: new BN(test_data.token_amount),
-            tokenQuantityPerTicket: new BN(test_data.unit_ticket_amount),
-            fundingDemand: new BN(test_data.funding_demand)
-          })
-          .accounts({
-            owner: sender.publicKey,
-            auction: auction,
-            auctionVault: auction_vault,
-            rent: SYSVAR_RENT_PUBKEY,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([sender])
-          .rpc();
+        const init_auc_tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+          name: auction_name,
+          ...saleTimes(start_time, 15, 25, [0, 10]),
+          fundingDemand: new BN(test_data.funding_demand),
+        }));
         console.log("init_auc_tx", init_auc_tx);
 
         const auction_data = program.account.auction.fetch(auction);
@@ -240,21 +257,7 @@ describe("anchor-latest", async () => {
     });
 
     it("Add Token!", async () => {
-        const add_token_tx = await program.methods.addToken()
-        .accounts({
-        owner: sender.publicKey,
-        auction: auction,
-        auctionVault: auction_vault,
-        ownerAuctionTokenAccount: sender_auctiontoken_ata,
-        auctionVaultTokenAccount: auction_vault_ata,
-        auctionToken: auction_token,
-        rent: SYSVAR_RENT_PUBKEY,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        clock: SYSVAR_CLOCK_PUBKEY,
-        }).signers([sender])
-        .rpc();
+        const add_token_tx = await addToken(program, sender, pdas, auction_token);
         console.log("add_token_tx", add_token_tx);
     });
 
@@ -262,12 +265,18 @@ describe("anchor-latest", async () => {
         const whitelist_tx = await program.methods.whitelist(
             {
               whitelisted: true,
+              tier: 0,
             }
           )
           .accounts({
               creator: sender.publicKey,
               whitelistPda: whitelist_pda,
               auction: auction,
+              config: config,
+              delegatePda: PublicKey.findProgramAddressSync(
+                [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([0])],
+                program.programId
+              )[0],
               whitelistUser: buyer.publicKey,
               rent: SYSVAR_RENT_PUBKEY,
               systemProgram: SystemProgram.programId,
@@ -276,17 +285,45 @@ describe("anchor-latest", async () => {
         console.log("whitelist_tx", whitelist_tx);
     });
 
+    it("Register!", async () => {
+        const register_tx = await program.methods.register()
+        .accounts({
+            buyer: buyer.publicKey,
+            auction: auction,
+            buyerPda: buyer_pda,
+            config: config,
+            clock: SYSVAR_CLOCK_PUBKEY,
+            systemProgram: SystemProgram.programId
+        }).signers([buyer])
+        .rpc();
+        console.log("register_tx", register_tx);
+    });
+
     it("PreSale Buy using SOL!", async () => {
-        const presale_buy_tx = await program.methods.preSaleBuyUsingSol()
+        const presale_buy_tx = await program.methods.buy({ preSale: {} }, null)
         .accounts({
             buyer: buyer.publicKey,
             buyerPda: buyer_pda,
+            referral: buyer.publicKey,
             buyerAuctionTokenAccount: buyer_auctiontoken_ata,
             auction: auction,
+            config: config,
             auctionVault: auction_vault,
             auctionVaultTokenAccount: auction_vault_ata,
             auctionToken: auction_token,
             whitelistPda: whitelist_pda,
+            stakeAllocation: PublicKey.findProgramAddressSync(
+              [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            priceFeed: PublicKey.default,
+            paymentOption: null,
+            bidToken: null,
+            buyerBidTokenAccount: null,
+            auctionVaultBidTokenAccount: null,
+            bidTokenProgram: null,
+            buyerRegistry: null,
             clock: SYSVAR_CLOCK_PUBKEY,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId
@@ -298,71 +335,68 @@ describe("anchor-latest", async () => {
   // assert(false)
   describe("Case 2: Init Auction(paywithSol), Add Token, Buy Token using Sol, Withdraw Funds!", async () => {
     it("Init Auction!", async () => {
+        await useNextAuction();
+
         // get the timestamp when auction goes LIVE
         const start_time = Math.floor(Date.now() / 1000);
         console.log("start_time:", start_time);
 
-        const init_auc_tx = await program.methods
-          .initAuction({
-            name: auction_pda_name,
-            enabled: true,
-            fixedAmount: true,
-            startTime: new BN(start_time + 7),
-            endTime: new BN(start_time + 14),
-            payWithNative: true,
-            preSale: true,
-            preSaleStartTime: new BN(start_time),
-            preSaleEndTime: new BN(start_time + 5),
-            tokensInPool: new BN(test_data.token_amount),
-            tokenQuantityPerTicket: new BN(test_data.unit_ticket_amount),
-            fundingDemand: new BN(test_data.funding_demand)
-          })
-          .accounts({
-            owner: sender.publicKey,
-            auction: auction,
-            auctionVault: auction_vault,
-            rent: SYSVAR_RENT_PUBKEY,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([sender])
-          .rpc();
+        const init_auc_tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+          name: auction_name,
+          ...saleTimes(start_time, 7, 14, [0, 5]),
+          fundingDemand: new BN(test_data.funding_demand),
+        }));
         console.log("init_auc_tx", init_auc_tx);
     });
 
     it("Add Token!", async () => {
-        const add_token_tx = await program.methods.addToken()
+        const add_token_tx = await addToken(program, sender, pdas, auction_token);
+        console.log("add_token_tx", add_token_tx);
+    });
+
+    it("Register!", async () => {
+        const register_tx = await program.methods.register()
         .accounts({
-        owner: sender.publicKey,
-        auction: auction,
-        auctionVault: auction_vault,
-        ownerAuctionTokenAccount: sender_auctiontoken_ata,
-        auctionVaultTokenAccount: auction_vault_ata,
-        auctionToken: auction_token,
-        rent: SYSVAR_RENT_PUBKEY,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        clock: SYSVAR_CLOCK_PUBKEY,
-        }).signers([sender])
+            buyer: buyer.publicKey,
+            auction: auction,
+            buyerPda: buyer_pda,
+            config: config,
+            clock: SYSVAR_CLOCK_PUBKEY,
+            systemProgram: SystemProgram.programId
+        }).signers([buyer])
         .rpc();
-        console.log("add_token_tx", add_token_tx);
+        console.log("register_tx", register_tx);
     });
 
     it("Buy Tokens using Sol!", async () => {
         console.log("Lets wait for Auction to go LIVE...")
         await delay(7000);
 
-        const buy_token_using_spl_tx = await program.methods.buyTokenUsingSol()
+        const buy_token_using_spl_tx = await program.methods.buy({ public: {} }, null)
         .accounts({
             buyer: buyer.publicKey,
             auction: auction,
+            config: config,
             auctionVault: auction_vault,
             buyerPda: buyer_pda,
+            referral: buyer.publicKey,
+            whitelistPda: whitelist_pda,
             auctionVaultTokenAccount: auction_vault_ata,
             buyerAuctionTokenAccount: buyer_auctiontoken_ata,
             auctionToken: auction_token,
             tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            stakeAllocation: PublicKey.findProgramAddressSync(
+              [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            priceFeed: PublicKey.default,
+            paymentOption: null,
+            bidToken: null,
+            buyerBidTokenAccount: null,
+            auctionVaultBidTokenAccount: null,
+            bidTokenProgram: null,
+            buyerRegistry: null,
             clock: SYSVAR_CLOCK_PUBKEY,
             systemProgram: SystemProgram.programId
           }).signers([buyer])
@@ -379,9 +413,20 @@ describe("anchor-latest", async () => {
         creator: sender.publicKey,
         auction: auction,
         auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
         auctionVaultTokenAccount: auction_vault_ata,
         creatorAuctionTokenAccount: sender_auctiontoken_ata,
         auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
         tokenProgram: TOKEN_PROGRAM_ID,
         clock: SYSVAR_CLOCK_PUBKEY,
         systemProgram: SystemProgram.programId
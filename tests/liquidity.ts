@@ -0,0 +1,485 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  getMint,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("liquidity", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Share of the SOL proceeds seeded into the pool, and the tokens paired with it
+  const liquidity_bps = 5000;
+  const liquidity_tokens = 1000;
+  // Seconds the owner has to seed the pool and withdraw before anyone can
+  const grace_period = 10;
+  // Seconds after the end of the sale the LP tokens stay locked for
+  const lp_lock = 60;
+  // Swap fee of the pools, as in state/pool.rs
+  const pool_fee_bps = 30;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner and the LP owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // This buyer also trades against the pool
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+  const buyer_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, buyer.publicKey);
+
+  // The pool and LP accounts of an auction's liquidity
+  const withPool = async (pdas: { auction: PublicKey; auction_vault: PublicKey; metadata: PublicKey }) => {
+    const pool = PublicKey.findProgramAddressSync(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("pool")), pdas.auction.toBuffer()],
+      program.programId
+    )[0];
+    const lp_mint = PublicKey.findProgramAddressSync(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("lp_mint")), pool.toBuffer()],
+      program.programId
+    )[0];
+    return {
+      ...pdas,
+      auction_vault_ata: await getAssociatedTokenAddress(auction_token, pdas.auction_vault, true),
+      pool,
+      lp_mint,
+      pool_ata: await getAssociatedTokenAddress(auction_token, pool, true),
+      pool_lp_ata: await getAssociatedTokenAddress(lp_mint, pool, true),
+    };
+  };
+  // The first auction is seeded by anyone after the grace period, then traded against,
+  // the second one is seeded by its owner, who then withdraws the rest of the proceeds
+  const seeded = await withPool(await nextAuctionPdas(program));
+  const withdrawn = await withPool(await nextAuctionPdas(program, 1));
+  const auctions = [seeded, withdrawn];
+  const { pool, lp_mint, pool_ata, pool_lp_ata } = seeded;
+  const sender_lp_ata = await getAssociatedTokenAddress(lp_mint, sender.publicKey);
+
+  // Sale schedule, set when the auctions are created
+  const schedule = {
+    end_time: 0,
+  };
+
+  // Output of a swap into the pool, as Pool::swap_output computes it
+  const swapOutput = (amount_in: BN, reserve_in: BN, reserve_out: BN) => {
+    const in_after_fee = amount_in.muln(10000 - pool_fee_bps);
+    return in_after_fee.mul(reserve_out).div(reserve_in.muln(10000).add(in_after_fee));
+  };
+
+  const tokenBalance = async (token_account: PublicKey) =>
+    new BN((await provider.connection.getTokenAccountBalance(token_account)).value.amount);
+
+  // Lamports `account` received in the transaction, leaving out the fee it paid for it
+  const received = async (tx: string, account: PublicKey) => {
+    const info = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const index = info.transaction.message.staticAccountKeys.findIndex((key) => key.equals(account));
+    const fee = index == 0 ? info.meta.fee : 0;
+    return info.meta.postBalances[index] - info.meta.preBalances[index] + fee;
+  };
+
+  it("Init Auctions!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+    const sale_times = saleTimes(start_time, 16, 30, [10, 13]);
+    schedule.end_time = sale_times.endTime.toNumber();
+
+    for (const [index, pdas] of auctions.entries()) {
+      const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+        name: "lampbit-auction-liquidity-" + index,
+        ...sale_times,
+        liquidityBps: liquidity_bps,
+        liquidityTokens: new BN(liquidity_tokens),
+        lpUnlockTime: new BN(schedule.end_time + lp_lock),
+        gracePeriod: new BN(grace_period),
+      }));
+      console.log("init_auc_tx", tx);
+
+      const add_token_tx = await addToken(program, sender, pdas, auction_token);
+      console.log("add_token_tx", add_token_tx);
+    }
+  });
+
+  it("Buy in both auctions!", async () => {
+    await getOrCreateAssociatedTokenAccount(provider.connection, buyer, auction_token, buyer.publicKey);
+
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(18000);
+
+    for (const { auction, auction_vault, auction_vault_ata } of auctions) {
+      const tx = await program.methods
+        .buy({ public: {} }, null)
+        .accounts({
+          buyer: buyer.publicKey,
+          buyerPda: PublicKey.findProgramAddressSync(
+            [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+            program.programId
+          )[0],
+          referral: buyer.publicKey,
+          buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+          auction: auction,
+          config: config,
+          auctionVault: auction_vault,
+          auctionVaultTokenAccount: auction_vault_ata,
+          auctionToken: auction_token,
+          whitelistPda: PublicKey.findProgramAddressSync(
+            [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+            program.programId
+          )[0],
+          stakeAllocation: PublicKey.findProgramAddressSync(
+            [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          priceFeed: PublicKey.default,
+          paymentOption: null,
+          bidToken: null,
+          buyerBidTokenAccount: null,
+          auctionVaultBidTokenAccount: null,
+          bidTokenProgram: null,
+          buyerRegistry: null,
+          clock: SYSVAR_CLOCK_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+      console.log("buy_tx", tx);
+    }
+  });
+
+  // Sent by the provider wallet, which is not the auction owner, unless signed by the owner
+  const finalizeWithLiquidity = async (pdas: typeof seeded, owner: Keypair | null = null) =>
+    program.methods
+      .finalizeWithLiquidity()
+      .accounts({
+        payer: owner ? owner.publicKey : provider.wallet.publicKey,
+        auction: pdas.auction,
+        auctionVault: pdas.auction_vault,
+        auctionVaultTokenAccount: pdas.auction_vault_ata,
+        auctionToken: auction_token,
+        pool: pdas.pool,
+        poolTokenAccount: pdas.pool_ata,
+        lpMint: pdas.lp_mint,
+        poolLpTokenAccount: pdas.pool_lp_ata,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        auctionTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers(owner ? [owner] : [])
+      .rpc();
+
+  const withdrawFunds = async () =>
+    program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: withdrawn.auction,
+        auctionVault: withdrawn.auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), withdrawn.auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: withdrawn.auction_vault_ata,
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), withdrawn.auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+  const swap = async (amount_in: BN, min_amount_out: BN, sol_in: boolean) =>
+    program.methods
+      .swap({ amountIn: amount_in, minAmountOut: min_amount_out, solIn: sol_in })
+      .accounts({
+        user: buyer.publicKey,
+        pool: pool,
+        poolTokenAccount: pool_ata,
+        userTokenAccount: buyer_auctiontoken_ata,
+        tokenMint: auction_token,
+        config: config,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc();
+
+  const claimLpTokens = async () =>
+    program.methods
+      .claimLpTokens()
+      .accounts({
+        lpOwner: sender.publicKey,
+        pool: pool,
+        lpMint: lp_mint,
+        poolLpTokenAccount: pool_lp_ata,
+        ownerLpTokenAccount: sender_lp_ata,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([sender])
+      .rpc();
+
+  it("Reject seeding by anyone but the owner during the grace period!", async () => {
+    console.log("Waiting for Auction to End...");
+    await delay((schedule.end_time + 2) * 1000 - Date.now());
+
+    try {
+      await finalizeWithLiquidity(seeded);
+      assert.fail("the pool was seeded by a wallet that is not the owner during the grace period");
+    } catch (err) {
+      assert.include(err.toString(), "GracePeriodNotOver");
+    }
+  });
+
+  it("Reject a withdrawal before the pool is seeded!", async () => {
+    try {
+      await withdrawFunds();
+      assert.fail("the proceeds were withdrawn before the promised liquidity was added");
+    } catch (err) {
+      assert.include(err.toString(), "LiquidityNotAdded");
+    }
+  });
+
+  it("Seed the pool and withdraw the rest of the proceeds, each paying its share of the fee!", async () => {
+    const auction_data = await program.account.auction.fetch(withdrawn.auction);
+    const fee = (amount: number) => Math.floor((amount * auction_data.feeBps) / 10000);
+    const sol_collected = auction_data.solCollected.toNumber();
+    const pool_share = Math.floor((sol_collected * liquidity_bps) / 10000);
+    const fee_treasury = (await program.account.config.fetch(config)).feeTreasury;
+
+    // The owner doesn't wait for the grace period, the pool's share pays its own fee
+    const seed_tx = await finalizeWithLiquidity(withdrawn, sender);
+    console.log("finalize_with_liquidity_tx", seed_tx);
+    assert.equal(await received(seed_tx, fee_treasury), fee(pool_share));
+    assert.equal((await program.account.pool.fetch(withdrawn.pool)).solReserve.toNumber(), pool_share - fee(pool_share));
+    assert.equal((await program.account.auction.fetch(withdrawn.auction)).solCollected.toNumber(), sol_collected - pool_share);
+
+    // The rest of the proceeds pays its fee at settlement, the vault closes with nothing owed
+    const rest = sol_collected - pool_share;
+    const rent_reserve = await provider.connection.getMinimumBalanceForRentExemption(
+      (await provider.connection.getAccountInfo(withdrawn.auction_vault)).data.length
+    );
+    assert.equal(await provider.connection.getBalance(withdrawn.auction_vault), rent_reserve + rest);
+    const withdraw_tx = await withdrawFunds();
+    console.log("withdraw_funds_tx", withdraw_tx);
+    assert.equal(await received(withdraw_tx, fee_treasury), fee(rest));
+    assert.equal(await received(withdraw_tx, sender.publicKey), rest - fee(rest) + rent_reserve);
+    assert.isNull(await provider.connection.getAccountInfo(withdrawn.auction_vault));
+    assert.isTrue((await program.account.auction.fetch(withdrawn.auction)).finalizedAt.gtn(0));
+  });
+
+  it("Seed the pool and finalize without the owner!", async () => {
+    console.log("Waiting for the grace period to pass...");
+    await delay((schedule.end_time + grace_period + 2) * 1000 - Date.now());
+
+    const auction_data = await program.account.auction.fetch(seeded.auction);
+    const pool_share = Math.floor((auction_data.solCollected.toNumber() * liquidity_bps) / 10000);
+    const pool_fee = Math.floor((pool_share * auction_data.feeBps) / 10000);
+
+    const tx = await finalizeWithLiquidity(seeded);
+    console.log("finalize_with_liquidity_tx", tx);
+
+    // The LP tokens stay locked to the owner, not to whoever seeded the pool
+    const pool_data = await program.account.pool.fetch(pool);
+    assert.isTrue(pool_data.lpOwner.equals(sender.publicKey));
+    assert.isTrue((await program.account.auction.fetch(seeded.auction)).liquidityAdded);
+
+    // The reserves hold the pool's share of the proceeds and the liquidity tokens
+    const sol_reserve = pool_data.solReserve;
+    const token_reserve = pool_data.tokenReserve;
+    assert.equal(sol_reserve.toNumber(), pool_share - pool_fee);
+    assert.isTrue(token_reserve.eq(new BN(liquidity_tokens).mul(new BN(1e9))));
+    assert.isTrue((await tokenBalance(pool_ata)).eq(token_reserve));
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(
+      (await provider.connection.getAccountInfo(pool)).data.length
+    );
+    assert.equal(await provider.connection.getBalance(pool), rent + sol_reserve.toNumber());
+
+    // The LP supply is the integer square root of the reserves' product, all held by the pool
+    const lp_supply = pool_data.lpSupply;
+    const product = sol_reserve.mul(token_reserve);
+    assert.isTrue(lp_supply.mul(lp_supply).lte(product));
+    assert.isTrue(lp_supply.addn(1).mul(lp_supply.addn(1)).gt(product));
+    assert.equal((await getMint(provider.connection, lp_mint)).supply.toString(), lp_supply.toString());
+    assert.isTrue((await tokenBalance(pool_lp_ata)).eq(lp_supply));
+
+    const finalize_tx = await program.methods
+      .finalizeAuction()
+      .accounts({
+        auction: seeded.auction,
+        auctionVault: seeded.auction_vault,
+        proceedsDestination: sender.publicKey,
+        auctionVaultTokenAccount: seeded.auction_vault_ata,
+        unsoldTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), seeded.auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    console.log("finalize_auction_tx", finalize_tx);
+
+    assert.isTrue((await program.account.auction.fetch(seeded.auction)).finalizedAt.gtn(0));
+  });
+
+  it("Swap SOL for tokens!", async () => {
+    const pool_data = await program.account.pool.fetch(pool);
+    const amount_in = pool_data.solReserve.divn(10);
+    const expected = swapOutput(amount_in, pool_data.solReserve, pool_data.tokenReserve);
+
+    const sol_before = await provider.connection.getBalance(buyer.publicKey);
+    const tokens_before = await tokenBalance(buyer_auctiontoken_ata);
+    const tx = await swap(amount_in, expected, true);
+    console.log("swap_tx", tx);
+
+    assert.equal(sol_before - (await provider.connection.getBalance(buyer.publicKey)), amount_in.toNumber());
+    assert.isTrue((await tokenBalance(buyer_auctiontoken_ata)).sub(tokens_before).eq(expected));
+    const pool_after = await program.account.pool.fetch(pool);
+    assert.isTrue(pool_after.solReserve.eq(pool_data.solReserve.add(amount_in)));
+    assert.isTrue(pool_after.tokenReserve.eq(pool_data.tokenReserve.sub(expected)));
+  });
+
+  it("Swap tokens for SOL!", async () => {
+    const pool_data = await program.account.pool.fetch(pool);
+    const amount_in = pool_data.tokenReserve.divn(10);
+    const expected = swapOutput(amount_in, pool_data.tokenReserve, pool_data.solReserve);
+
+    const sol_before = await provider.connection.getBalance(buyer.publicKey);
+    const tokens_before = await tokenBalance(buyer_auctiontoken_ata);
+    const tx = await swap(amount_in, expected, false);
+    console.log("swap_tx", tx);
+
+    assert.equal((await provider.connection.getBalance(buyer.publicKey)) - sol_before, expected.toNumber());
+    assert.isTrue(tokens_before.sub(await tokenBalance(buyer_auctiontoken_ata)).eq(amount_in));
+    const pool_after = await program.account.pool.fetch(pool);
+    assert.isTrue(pool_after.tokenReserve.eq(pool_data.tokenReserve.add(amount_in)));
+    assert.isTrue(pool_after.solReserve.eq(pool_data.solReserve.sub(expected)));
+  });
+
+  it("Reject a swap below the minimum output!", async () => {
+    const pool_data = await program.account.pool.fetch(pool);
+    const amount_in = pool_data.solReserve.divn(10);
+    const expected = swapOutput(amount_in, pool_data.solReserve, pool_data.tokenReserve);
+
+    try {
+      await swap(amount_in, expected.addn(1), true);
+      assert.fail("the swap paid out less than the minimum output");
+    } catch (err) {
+      assert.include(err.toString(), "SlippageExceeded");
+    }
+  });
+
+  it("Reject claiming the LP tokens before the lock ends!", async () => {
+    try {
+      await claimLpTokens();
+      assert.fail("the LP tokens were claimed while locked");
+    } catch (err) {
+      assert.include(err.toString(), "LpLocked");
+    }
+  });
+
+  it("Claim the LP tokens once the lock ends!", async () => {
+    console.log("Waiting for the LP lock to end...");
+    await delay((schedule.end_time + lp_lock + 2) * 1000 - Date.now());
+
+    const lp_supply = (await program.account.pool.fetch(pool)).lpSupply;
+    const tx = await claimLpTokens();
+    console.log("claim_lp_tokens_tx", tx);
+
+    assert.isTrue((await tokenBalance(sender_lp_ata)).eq(lp_supply));
+    assert.isTrue((await tokenBalance(pool_lp_ata)).eqn(0));
+    assert.isTrue((await program.account.pool.fetch(pool)).lpClaimed);
+  });
+
+  it("Remove liquidity!", async () => {
+    const pool_data = await program.account.pool.fetch(pool);
+    const lp_amount = pool_data.lpSupply.divn(2);
+    const sol_share = pool_data.solReserve.mul(lp_amount).div(pool_data.lpSupply);
+    const token_share = pool_data.tokenReserve.mul(lp_amount).div(pool_data.lpSupply);
+
+    const sol_before = await provider.connection.getBalance(sender.publicKey);
+    const tokens_before = await tokenBalance(sender_auctiontoken_ata);
+    const tx = await program.methods
+      .removeLiquidity(lp_amount)
+      .accounts({
+        holder: sender.publicKey,
+        pool: pool,
+        lpMint: lp_mint,
+        holderLpTokenAccount: sender_lp_ata,
+        poolTokenAccount: pool_ata,
+        holderTokenAccount: sender_auctiontoken_ata,
+        tokenMint: auction_token,
+        config: config,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        auctionTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("remove_liquidity_tx", tx);
+
+    // The burnt LP tokens pay out their share of both reserves
+    assert.equal((await provider.connection.getBalance(sender.publicKey)) - sol_before, sol_share.toNumber());
+    assert.isTrue((await tokenBalance(sender_auctiontoken_ata)).sub(tokens_before).eq(token_share));
+    assert.isTrue((await tokenBalance(sender_lp_ata)).eq(pool_data.lpSupply.sub(lp_amount)));
+
+    const pool_after = await program.account.pool.fetch(pool);
+    assert.isTrue(pool_after.lpSupply.eq(pool_data.lpSupply.sub(lp_amount)));
+    assert.isTrue(pool_after.solReserve.eq(pool_data.solReserve.sub(sol_share)));
+    assert.isTrue(pool_after.tokenReserve.eq(pool_data.tokenReserve.sub(token_share)));
+  });
+});
@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+} from "@solana/web3.js";
+import fs from "fs";
+import { assert } from "chai";
+
+describe("migrate auction", async () => {
+  // Runs against the local validator, which loads the legacy auction from tests/fixtures
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  const payer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Anyone can migrate an auction
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+
+  // Created by the first program version, seeded by its name and without id, bumps or version
+  const legacy_name = "legacy-auction";
+  const legacy_owner = new PublicKey(Buffer.alloc(32, 7));
+  const [legacy_auction, legacy_bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("auction")), Buffer.from(anchor.utils.bytes.utf8.encode(legacy_name))],
+    program.programId
+  );
+  const [_legacy_auction_vault, legacy_vault_bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("auction_vault")), legacy_auction.toBuffer()],
+    program.programId
+  );
+  const [legacy_metadata, legacy_metadata_bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("metadata")), legacy_auction.toBuffer()],
+    program.programId
+  );
+  // Whitelisted before tiers, its PDA has no tier
+  const legacy_whitelisted = new PublicKey(Buffer.alloc(32, 9));
+  const [legacy_whitelist, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), legacy_whitelisted.toBuffer(), legacy_auction.toBuffer()],
+    program.programId
+  );
+
+  const migrateAuction = (whitelist_accounts: PublicKey[] = [legacy_whitelist, legacy_whitelisted]) =>
+    program.methods
+      .migrateAuction()
+      .accounts({
+        payer: payer.publicKey,
+        auction: legacy_auction,
+        metadata: legacy_metadata,
+        config: config,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(whitelist_accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .signers([payer])
+      .rpc();
+
+  it("Reject a whitelist PDA passed without its user!", async () => {
+    try {
+      await migrateAuction([legacy_whitelist]);
+      assert.fail("auction was migrated with a whitelist PDA missing its user");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidWhitelistAccounts");
+    }
+
+    // Nor with the user of another whitelist PDA
+    try {
+      await migrateAuction([legacy_whitelist, payer.publicKey]);
+      assert.fail("auction was migrated with a whitelist PDA of another user");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidWhitelistAccounts");
+    }
+  });
+
+  it("Migrate a name-seeded auction!", async () => {
+    const auction_count = (await program.account.config.fetch(config)).auctionCount;
+
+    const tx = await migrateAuction();
+    console.log("migrate_auction_tx", tx);
+
+    // The account grew to the current layout
+    const account = await provider.connection.getAccountInfo(legacy_auction);
+    assert.equal(account.data.length, program.account.auction.size);
+
+    // It keeps its address and fields, and gets the next id and its bumps
+    const auction_data = await program.account.auction.fetch(legacy_auction);
+    assert.equal(auction_data.name, legacy_name);
+    assert.isTrue(auction_data.owner.equals(legacy_owner));
+    assert.equal(auction_data.tokensInPool.toNumber(), 360000000);
+    assert.isTrue(auction_data.nameSeeded);
+    assert.equal(auction_data.id.toString(), auction_count.toString());
+    assert.equal(auction_data.bump, legacy_bump);
+    assert.equal(auction_data.vaultBump, legacy_vault_bump);
+
+    // The id is taken, so the next auction gets another one
+    const config_data = await program.account.config.fetch(config);
+    assert.equal(config_data.auctionCount.toString(), auction_count.addn(1).toString());
+
+    // It is charged the platform fee in force when it was migrated
+    assert.equal(auction_data.feeBps, config_data.feeBps);
+
+    // Fields added since are filled with their defaults
+    assert.equal(auction_data.version, 8);
+    assert.isTrue(auction_data.proceedsDestination.equals(legacy_owner));
+    assert.isTrue(auction_data.unsoldDestination.equals(legacy_owner));
+    assert.equal(auction_data.gracePeriod.toNumber(), 7 * 24 * 60 * 60);
+    assert.equal(auction_data.finalizedAt.toNumber(), 0);
+    assert.isTrue(auction_data.buyerRegistry.equals(PublicKey.default));
+    assert.deepEqual(auction_data.unsoldTokenPolicy, { return: {} });
+    assert.isTrue(auction_data.tokenMint.equals(PublicKey.default));
+
+    // It gets the empty metadata of a new auction
+    const metadata_data = await program.account.auctionMetadata.fetch(legacy_metadata);
+    assert.isTrue(metadata_data.auction.equals(legacy_auction));
+    assert.equal(metadata_data.bump, legacy_metadata_bump);
+    assert.equal(metadata_data.descriptionUri, "");
+    assert.equal(metadata_data.socialLinks.length, 0);
+  });
+
+  it("Keep the whitelisted user in the first tier!", async () => {
+    // The whitelist PDA grew to the current layout and loads with a tier
+    const account = await provider.connection.getAccountInfo(legacy_whitelist);
+    assert.equal(account.data.length, program.account.whitelist.size);
+
+    const whitelist_data = await program.account.whitelist.fetch(legacy_whitelist);
+    assert.isTrue(whitelist_data.whitelisted);
+    assert.equal(whitelist_data.tier, 0);
+  });
+
+  it("Migrate again without changes!", async () => {
+    const before = await provider.connection.getAccountInfo(legacy_auction);
+
+    const tx = await migrateAuction();
+    console.log("migrate_auction_tx", tx);
+
+    // Already current, so it is left untouched and keeps its id
+    const after = await provider.connection.getAccountInfo(legacy_auction);
+    assert.isTrue(before.data.equals(after.data));
+    assert.equal((await provider.connection.getAccountInfo(legacy_whitelist)).data.length, program.account.whitelist.size);
+  });
+});
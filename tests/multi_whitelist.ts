@@ -10,6 +10,7 @@ import { BN } from "bn.js";
 import fs from "fs";
 import { assert } from "chai";
 import * as anchor from "@coral-xyz/anchor";
+import { defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
 
 describe("multi whitelist", async () => {
   const connection = new anchor.web3.Connection(
@@ -36,24 +37,16 @@ describe("multi whitelist", async () => {
   const programId = program.programId;
   let addresses = [];
   let whitelist_pdas = [];
-  const auction_pda_name = "lampbit-auction-edge2";
-  const [auction, _] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(anchor.utils.bytes.utf8.encode("auction")),
-      Buffer.from(anchor.utils.bytes.utf8.encode(auction_pda_name)),
-    ],
+  const [config, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
     program.programId
   );
+  const auction_name = "lampbit-auction-edge2";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction } = pdas;
   console.log("auction:", auction.toString());
 
-  const [auction_vault, __] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from(anchor.utils.bytes.utf8.encode("auction_vault")),
-      auction.toBuffer(),
-    ],
-    program.programId
-  );
-
   // Test Data
   const test_data = {
     funding_demand: 1782,
@@ -66,30 +59,11 @@ describe("multi whitelist", async () => {
     const start_time = Math.floor(Date.now() / 1000);
     console.log("start_time:", start_time);
 
-    const init_auc_tx = await program.methods
-      .initAuction({
-        name: auction_pda_name,
-        enabled: true,
-        fixedAmount: true,
-        startTime: new BN(start_time + 15),
-        endTime: new BN(start_time + 25),
-        payWithNative: true,
-        preSale: true,
-        preSaleStartTime: new BN(start_time),
-        preSaleEndTime: new BN(start_time + 10),
-        tokensInPool: new BN(test_data.token_amount),
-        tokenQuantityPerTicket: new BN(test_data.unit_ticket_amount),
-        fundingDemand: new BN(test_data.funding_demand),
-      })
-      .accounts({
-        owner: sender.publicKey,
-        auction: auction,
-        auctionVault: auction_vault,
-        rent: SYSVAR_RENT_PUBKEY,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([sender])
-      .rpc();
+    const init_auc_tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 15, 25, [0, 10]),
+      fundingDemand: new BN(test_data.funding_demand),
+    }));
     console.log("init_auc_tx", init_auc_tx);
 
     const auction_data = program.account.auction.fetch(auction);
@@ -123,11 +97,17 @@ describe("multi whitelist", async () => {
       let ixn = await program.methods
         .whitelist({
           whitelisted: true,
+          tier: 0,
         })
         .accounts({
           creator: sender.publicKey,
           whitelistPda: whitelist_pdas[i],
           auction: auction,
+          config: config,
+          delegatePda: PublicKey.findProgramAddressSync(
+            [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([0])],
+            program.programId
+          )[0],
           whitelistUser: addresses[i],
           rent: SYSVAR_RENT_PUBKEY,
           systemProgram: SystemProgram.programId,
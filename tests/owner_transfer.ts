@@ -0,0 +1,292 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("owner transfer", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Seconds the owner has to withdraw after the end before anyone can finalize
+  const grace_period = 5;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the first auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Not proposed as owner
+  const new_owner = Keypair.generate();
+  const pauser = Keypair.generate(); // Granted the pauser role by the first owner
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-owner";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+
+  // Sale schedule, set when the auction is created
+  const schedule = {
+    end_time: 0,
+  };
+
+  // Role indices of DelegateRole
+  const PAUSER = 1;
+  const delegatePda = (authority: PublicKey, role: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), authority.toBuffer(), Buffer.from([role])],
+      program.programId
+    )[0];
+
+  const proposeOwner = async (owner: Keypair, proposed: PublicKey) =>
+    program.methods
+      .proposeOwner(proposed)
+      .accounts({
+        owner: owner.publicKey,
+        auction: auction,
+        config: config,
+      })
+      .signers([owner])
+      .rpc();
+
+  const acceptOwner = async (acceptor: Keypair) =>
+    program.methods
+      .acceptOwner()
+      .accounts({
+        newOwner: acceptor.publicKey,
+        auction: auction,
+        // Only required once a team allocation is locked
+        teamVesting: null,
+        config: config,
+      })
+      .signers([acceptor])
+      .rpc();
+
+  const setAuctionEnabled = async (authority: Keypair, enabled: boolean) =>
+    program.methods
+      .setAuctionEnabled(enabled)
+      .accounts({
+        authority: authority.publicKey,
+        auction: auction,
+        delegatePda: delegatePda(authority.publicKey, PAUSER),
+        config: config,
+      })
+      .signers([authority])
+      .rpc();
+
+  // Anyone can send it, here the provider wallet rather than an owner
+  const finalizeAuction = async (destination: PublicKey) =>
+    program.methods
+      .finalizeAuction()
+      .accounts({
+        auction: auction,
+        auctionVault: auction_vault,
+        proceedsDestination: destination,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        unsoldTokenAccount: await getAssociatedTokenAddress(auction_token, destination),
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+    const sale_times = saleTimes(start_time, 20, 30, [15, 18]);
+    schedule.end_time = sale_times.endTime.toNumber();
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...sale_times,
+      gracePeriod: new BN(grace_period),
+    }));
+    console.log("init_auc_tx", tx);
+
+    const add_token_tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", add_token_tx);
+  });
+
+  const grantPauser = async () =>
+    program.methods
+      .grantRole({ delegate: pauser.publicKey, role: { pauser: {} }, destination: PublicKey.default })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        delegatePda: delegatePda(pauser.publicKey, PAUSER),
+        config: config,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+  it("Grant a pauser role!", async () => {
+    const tx = await grantPauser();
+    console.log("grant_role_tx", tx);
+
+    // The pauser acts for the owner who granted the role
+    console.log("set_auction_enabled_tx", await setAuctionEnabled(pauser, false));
+    assert.isFalse((await program.account.auction.fetch(auction)).enabled);
+    console.log("set_auction_enabled_tx", await setAuctionEnabled(sender, true));
+  });
+
+  it("Revoke the pauser role!", async () => {
+    const tx = await program.methods
+      .revokeRole({ delegate: pauser.publicKey, role: { pauser: {} } })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        delegatePda: delegatePda(pauser.publicKey, PAUSER),
+        config: config,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("revoke_role_tx", tx);
+    assert.isNull(await provider.connection.getAccountInfo(delegatePda(pauser.publicKey, PAUSER)));
+
+    // The revoked pauser can no longer act for the owner
+    try {
+      await setAuctionEnabled(pauser, false);
+      assert.fail("a revoked pauser disabled the auction");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+    assert.isTrue((await program.account.auction.fetch(auction)).enabled);
+
+    // Granted again, for the transfer to take it away
+    console.log("grant_role_tx", await grantPauser());
+  });
+
+  it("Propose a new owner!", async () => {
+    const tx = await proposeOwner(sender, new_owner.publicKey);
+    console.log("propose_owner_tx", tx);
+
+    // The owner only changes once the new owner accepts
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isTrue(auction_data.owner.equals(sender.publicKey));
+    assert.isTrue(auction_data.pendingOwner.equals(new_owner.publicKey));
+  });
+
+  it("Reject accepting by anyone but the proposed owner!", async () => {
+    try {
+      await acceptOwner(buyer);
+      assert.fail("ownership was accepted by a wallet that was not proposed");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isTrue(auction_data.owner.equals(sender.publicKey));
+    assert.isTrue(auction_data.pendingOwner.equals(new_owner.publicKey));
+  });
+
+  it("Accept the ownership!", async () => {
+    const tx = await acceptOwner(new_owner);
+    console.log("accept_owner_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isTrue(auction_data.owner.equals(new_owner.publicKey));
+    assert.isTrue(auction_data.pendingOwner.equals(PublicKey.default));
+
+    // The destinations left to the previous owner follow the ownership
+    assert.isTrue(auction_data.proceedsDestination.equals(new_owner.publicKey));
+    assert.isTrue(auction_data.unsoldDestination.equals(new_owner.publicKey));
+
+    // It can't be accepted twice
+    try {
+      await acceptOwner(new_owner);
+      assert.fail("ownership was accepted twice");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+  });
+
+  it("Lock the previous owner and their delegates out!", async () => {
+    try {
+      await proposeOwner(sender, sender.publicKey);
+      assert.fail("the previous owner proposed an owner");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    try {
+      await setAuctionEnabled(sender, false);
+      assert.fail("the previous owner disabled the auction");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    // Roles granted by the previous owner lapse with the transfer
+    try {
+      await setAuctionEnabled(pauser, false);
+      assert.fail("a pauser of the previous owner disabled the auction");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    assert.isTrue((await program.account.auction.fetch(auction)).enabled);
+
+    // While the new owner is in charge
+    console.log("set_auction_enabled_tx", await setAuctionEnabled(new_owner, false));
+    assert.isFalse((await program.account.auction.fetch(auction)).enabled);
+  });
+
+  it("Finalize to the new owner!", async () => {
+    console.log("Waiting for the grace period to pass...");
+    await delay((schedule.end_time + grace_period + 2) * 1000 - Date.now());
+
+    const new_owner_ata = await getOrCreateAssociatedTokenAccount(
+      provider.connection, sender, auction_token, new_owner.publicKey
+    );
+
+    // The previous owner is no longer paid by the crank
+    try {
+      await finalizeAuction(sender.publicKey);
+      assert.fail("the auction was finalized to the previous owner");
+    } catch (err) {
+      assert.include(err.toString(), "ConstraintAddress");
+    }
+
+    const tx = await finalizeAuction(new_owner.publicKey);
+    console.log("finalize_auction_tx", tx);
+
+    // Nothing was sold, so the whole pool goes back to the new owner
+    const pool = new BN(360000000).mul(new BN(LAMPORTS_PER_SOL));
+    const balance = (await provider.connection.getTokenAccountBalance(new_owner_ata.address)).value.amount;
+    assert.equal(balance, pool.toString());
+    assert.isTrue((await program.account.auction.fetch(auction)).finalizedAt.gtn(0));
+  });
+});
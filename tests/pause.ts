@@ -0,0 +1,330 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("pause", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Instruction classes of Config.pause_flags, as in state/config.rs
+  const PAUSE_BUYS = 1 << 0;
+  const PAUSE_WITHDRAWALS = 1 << 1;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner and the proceeds destination
+  const buyer = Keypair.generate();
+  const referrer = Keypair.generate();
+  // The admin the provider wallet hands the config over to, and back from
+  const new_admin = Keypair.generate();
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-pause";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const referralPda = (wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(anchor.utils.bytes.utf8.encode("referral")),
+        auction.toBuffer(),
+        wallet.toBuffer(),
+        PublicKey.default.toBuffer(),
+      ],
+      program.programId
+    )[0];
+  const sol_referral = referralPda(referrer.publicKey);
+
+  // Sale schedule, set when the auction is created
+  const schedule = {
+    end_time: 0,
+  };
+
+  const setPause = async (paused: boolean, pause_flags: number, admin: Keypair | null = null) =>
+    program.methods
+      .setPause({ paused, pauseFlags: pause_flags })
+      .accounts({
+        admin: admin ? admin.publicKey : provider.wallet.publicKey,
+        config: config,
+      })
+      .signers(admin ? [admin] : [])
+      .rpc();
+
+  const registerReferrer = async (wallet: Keypair) =>
+    program.methods
+      .registerReferrer(PublicKey.default)
+      .accounts({
+        referrer: wallet.publicKey,
+        auction: auction,
+        referral: referralPda(wallet.publicKey),
+        config: config,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const buy = async () =>
+    program.methods
+      .buy({ public: {} }, referrer.publicKey)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: sol_referral,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, buyer.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+  const withdrawFunds = async () =>
+    program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: auction_vault_ata,
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+  const claimReferralReward = async () =>
+    program.methods
+      .claimReferralReward()
+      .accounts({
+        referrer: referrer.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        proceedsDestination: sender.publicKey,
+        referral: sol_referral,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([referrer])
+      .rpc();
+
+  // Run `instruction` and expect it to fail with `error`
+  const assertRejected = async (instruction: () => Promise<string>, error: string) => {
+    try {
+      await instruction();
+      assert.fail("the instruction succeeded instead of failing with " + error);
+    } catch (err) {
+      assert.include(err.toString(), error);
+    }
+  };
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+    const sale_times = saleTimes(start_time, 16, 50, [10, 13]);
+    schedule.end_time = sale_times.endTime.toNumber();
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...sale_times,
+      referralBps: 1000,
+    }));
+    console.log("init_auc_tx", tx);
+
+    const add_token_tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", add_token_tx);
+
+    // Fund the buyer and the referrer
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: sender.publicKey, toPubkey: buyer.publicKey, lamports: LAMPORTS_PER_SOL / 5 }),
+        SystemProgram.transfer({ fromPubkey: sender.publicKey, toPubkey: referrer.publicKey, lamports: LAMPORTS_PER_SOL / 20 })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, buyer.publicKey);
+
+    console.log("register_referrer_tx", await registerReferrer(referrer));
+  });
+
+  it("Reject a pause by anyone but the admin!", async () => {
+    await assertRejected(() => setPause(true, 0, buyer), "Unauthorized");
+    assert.isFalse((await program.account.config.fetch(config)).paused);
+  });
+
+  it("Reject buys, withdrawals and claims under a full pause!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(18000);
+
+    console.log("set_pause_tx", await setPause(true, 0));
+
+    await assertRejected(buy, "ProgramPaused");
+    await assertRejected(withdrawFunds, "ProgramPaused");
+    await assertRejected(claimReferralReward, "ProgramPaused");
+    // Instructions outside of the paused classes are stopped as well
+    await assertRejected(() => registerReferrer(buyer), "ProgramPaused");
+  });
+
+  it("Reject only buys while buys are paused!", async () => {
+    console.log("set_pause_tx", await setPause(false, PAUSE_BUYS));
+
+    await assertRejected(buy, "BuysPaused");
+
+    // Registering a referrer is not a buy and still works
+    console.log("register_referrer_tx", await registerReferrer(buyer));
+  });
+
+  it("Reject only withdrawals while withdrawals are paused!", async () => {
+    console.log("set_pause_tx", await setPause(false, PAUSE_WITHDRAWALS));
+
+    // The pause is checked before the end of the sale, so both are rejected by it
+    await assertRejected(withdrawFunds, "WithdrawalsPaused");
+    await assertRejected(claimReferralReward, "WithdrawalsPaused");
+
+    // Buying still works, and rewards the referrer
+    console.log("buy_tx", await buy());
+    assert.isAbove((await program.account.referral.fetch(sol_referral)).reward.toNumber(), 0);
+  });
+
+  it("Withdraw and claim once unpaused!", async () => {
+    console.log("set_pause_tx", await setPause(false, 0));
+    const config_data = await program.account.config.fetch(config);
+    assert.isFalse(config_data.paused);
+    assert.equal(config_data.pauseFlags, 0);
+
+    console.log("Waiting for Auction to End...");
+    await delay((schedule.end_time + 2) * 1000 - Date.now());
+
+    console.log("withdraw_funds_tx", await withdrawFunds());
+    assert.isTrue((await program.account.auction.fetch(auction)).finalizedAt.gtn(0));
+
+    console.log("claim_referral_reward_tx", await claimReferralReward());
+    assert.equal((await program.account.referral.fetch(sol_referral)).reward.toNumber(), 0);
+  });
+
+  it("Hand the admin over in two steps!", async () => {
+    const tx = await program.methods
+      .proposeAdmin(new_admin.publicKey)
+      .accounts({ admin: provider.wallet.publicKey, config: config })
+      .rpc();
+    console.log("propose_admin_tx", tx);
+
+    let config_data = await program.account.config.fetch(config);
+    assert.isTrue(config_data.admin.equals(provider.wallet.publicKey));
+    assert.isTrue(config_data.pendingAdmin.equals(new_admin.publicKey));
+
+    // Only the proposed admin can accept
+    await assertRejected(
+      () =>
+        program.methods
+          .acceptAdmin()
+          .accounts({ newAdmin: buyer.publicKey, config: config })
+          .signers([buyer])
+          .rpc(),
+      "Unauthorized"
+    );
+
+    const accept_tx = await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: new_admin.publicKey, config: config })
+      .signers([new_admin])
+      .rpc();
+    console.log("accept_admin_tx", accept_tx);
+
+    config_data = await program.account.config.fetch(config);
+    assert.isTrue(config_data.admin.equals(new_admin.publicKey));
+    assert.isTrue(config_data.pendingAdmin.equals(PublicKey.default));
+
+    // The old admin has lost access, the new one has it
+    await assertRejected(() => setPause(true, 0), "Unauthorized");
+    console.log("set_pause_tx", await setPause(false, 0, new_admin));
+  });
+
+  it("Hand the admin back!", async () => {
+    const tx = await program.methods
+      .proposeAdmin(provider.wallet.publicKey)
+      .accounts({ admin: new_admin.publicKey, config: config })
+      .signers([new_admin])
+      .rpc();
+    console.log("propose_admin_tx", tx);
+
+    const accept_tx = await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: provider.wallet.publicKey, config: config })
+      .rpc();
+    console.log("accept_admin_tx", accept_tx);
+
+    assert.isTrue((await program.account.config.fetch(config)).admin.equals(provider.wallet.publicKey));
+  });
+});
@@ -0,0 +1,285 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("payment options", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Token accepted as payment alongside SOL
+  const bid_token = new PublicKey("6YMTJpgraqrd68mBfjkwG65FPuHiZWuifi4UP1WUoHjK");
+  // Bid token base units for one ticket
+  const price_per_ticket = 1000000;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Pays in bid tokens
+  const buyer_bidtoken_ata = new PublicKey("5Q3NSjAYBFNyWL6sJkiz7YidYpBqgxrYTK5nnrfkzFcR");
+  const sol_buyer = Keypair.generate(); // Pays in SOL
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-payment";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const auction_vault_bidtoken_ata = await getAssociatedTokenAddress(bid_token, auction_vault, true);
+  const [payment_option, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("payment_option")), auction.toBuffer(), bid_token.toBuffer()],
+    program.programId
+  );
+  const buyer_registry = Keypair.generate();
+
+  const tokenBalance = async (token_account: PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(token_account)).value.amount);
+
+  const buy = async (wallet: Keypair, spl: boolean) =>
+    program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: wallet.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: wallet.publicKey,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, wallet.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        // Bid token accounts are passed to pay in the payment option's mint, left out for SOL
+        paymentOption: spl ? payment_option : null,
+        bidToken: spl ? bid_token : null,
+        buyerBidTokenAccount: spl ? buyer_bidtoken_ata : null,
+        auctionVaultBidTokenAccount: spl ? auction_vault_bidtoken_ata : null,
+        bidTokenProgram: spl ? TOKEN_PROGRAM_ID : null,
+        buyerRegistry: buyer_registry.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 16, 45, [10, 13]),
+    }));
+    console.log("init_auc_tx", tx);
+  });
+
+  it("Add Token!", async () => {
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Add Payment Option!", async () => {
+    const tx = await program.methods
+      .addPaymentOption({ pricePerTicket: new BN(price_per_ticket) })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        config: config,
+        paymentOption: payment_option,
+        auctionVaultBidTokenAccount: auction_vault_bidtoken_ata,
+        bidToken: bid_token,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("add_payment_option_tx", tx);
+  });
+
+  it("Init Buyer Registry!", async () => {
+    const tx = await program.methods
+      .initBuyerRegistry()
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        buyerRegistry: buyer_registry.publicKey,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .preInstructions([await program.account.buyerRegistry.createInstruction(buyer_registry)])
+      .signers([sender, buyer_registry])
+      .rpc();
+    console.log("init_buyer_registry_tx", tx);
+
+    // Fund the SOL buyer and create its auction token account
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: sol_buyer.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, sol_buyer.publicKey);
+  });
+
+  it("Buy with the payment option's mint!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(18000);
+
+    const buyer_before = await tokenBalance(buyer_bidtoken_ata);
+    const vault_before = await tokenBalance(auction_vault_bidtoken_ata);
+
+    const tx = await buy(buyer, true);
+    console.log("buy_tx", tx);
+
+    // The ticket is paid in bid tokens, tracked on the payment option only
+    assert.equal(buyer_before - (await tokenBalance(buyer_bidtoken_ata)), price_per_ticket);
+    assert.equal((await tokenBalance(auction_vault_bidtoken_ata)) - vault_before, price_per_ticket);
+    const payment_option_data = await program.account.paymentOption.fetch(payment_option);
+    assert.equal(payment_option_data.totalCollected.toNumber(), price_per_ticket);
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.equal(auction_data.solCollected.toNumber(), 0);
+
+    // The registry lists the payment in its currency
+    const registry = await program.account.buyerRegistry.fetch(buyer_registry.publicKey);
+    assert.isTrue(registry.entries[0].buyer.equals(buyer.publicKey));
+    assert.equal(registry.entries[0].solPaid.toNumber(), 0);
+    assert.equal(registry.entries[0].tokenPaid.toNumber(), price_per_ticket);
+    assert.isTrue(registry.entries[0].paidMint.equals(bid_token));
+  });
+
+  it("Buy with SOL!", async () => {
+    const tx = await buy(sol_buyer, false);
+    console.log("buy_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.isAbove(auction_data.solCollected.toNumber(), 0);
+    const payment_option_data = await program.account.paymentOption.fetch(payment_option);
+    assert.equal(payment_option_data.totalCollected.toNumber(), price_per_ticket);
+
+    const registry = await program.account.buyerRegistry.fetch(buyer_registry.publicKey);
+    assert.equal(registry.entries[1].solPaid.toNumber(), auction_data.solCollected.toNumber());
+    assert.equal(registry.entries[1].tokenPaid.toNumber(), 0);
+  });
+
+  it("Withdraw every currency!", async () => {
+    console.log("Waiting for Auction to End...");
+    await delay(30000);
+
+    const config_data = await program.account.config.fetch(config);
+    const destination_bidtoken_ata = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, sender, bid_token, sender.publicKey)
+    ).address;
+    const treasury_bidtoken_ata = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, sender, bid_token, config_data.feeTreasury)
+    ).address;
+    const destination_before = await tokenBalance(destination_bidtoken_ata);
+    const treasury_before = await tokenBalance(treasury_bidtoken_ata);
+    const auction_data = await program.account.auction.fetch(auction);
+
+    const tx = await program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: auction_vault_ata,
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: config_data.feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        [
+          payment_option,
+          auction_vault_bidtoken_ata,
+          destination_bidtoken_ata,
+          treasury_bidtoken_ata,
+          bid_token,
+          TOKEN_PROGRAM_ID,
+        ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: !pubkey.equals(TOKEN_PROGRAM_ID) }))
+      )
+      .signers([sender])
+      .rpc();
+    console.log("withdraw_funds_tx", tx);
+
+    // The bid tokens go to the owner and the treasury, less and by the fee of the auction
+    const spl_fee = Math.floor((price_per_ticket * auction_data.feeBps) / 10000);
+    assert.equal((await tokenBalance(destination_bidtoken_ata)) - destination_before, price_per_ticket - spl_fee);
+    assert.equal((await tokenBalance(treasury_bidtoken_ata)) - treasury_before, spl_fee);
+    assert.equal(await tokenBalance(auction_vault_bidtoken_ata), 0);
+    const payment_option_data = await program.account.paymentOption.fetch(payment_option);
+    assert.equal(payment_option_data.totalCollected.toNumber(), 0);
+
+    // The SOL went out too, nothing is owed from the vault so it is closed
+    assert.isNull(await provider.connection.getAccountInfo(auction_vault));
+  });
+});
@@ -0,0 +1,348 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("referral", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Token accepted as payment alongside SOL
+  const bid_token = new PublicKey("6YMTJpgraqrd68mBfjkwG65FPuHiZWuifi4UP1WUoHjK");
+  // Bid token base units for one ticket
+  const price_per_ticket = 1000000;
+  // Share of referred purchases rewarded to the referrer
+  const referral_bps = 1000;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Pays in bid tokens
+  const buyer_bidtoken_ata = new PublicKey("5Q3NSjAYBFNyWL6sJkiz7YidYpBqgxrYTK5nnrfkzFcR");
+  const sol_buyer = Keypair.generate(); // Pays in SOL
+  const referrer = Keypair.generate(); // Refers both buyers
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+  const referrer_bidtoken_ata = await getAssociatedTokenAddress(bid_token, referrer.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-referral";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const auction_vault_bidtoken_ata = await getAssociatedTokenAddress(bid_token, auction_vault, true);
+  const [payment_option, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("payment_option")), auction.toBuffer(), bid_token.toBuffer()],
+    program.programId
+  );
+
+  // Referral PDAs of the referrer, one per currency, the default mint for SOL
+  const referralPda = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(anchor.utils.bytes.utf8.encode("referral")), auction.toBuffer(), referrer.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+  const sol_referral = referralPda(PublicKey.default);
+  const spl_referral = referralPda(bid_token);
+
+  const tokenBalance = async (token_account: PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(token_account)).value.amount);
+
+  const reward = (amount: number) => Math.floor((amount * referral_bps) / 10000);
+
+  const buy = async (wallet: Keypair, spl: boolean) =>
+    program.methods
+      .buy({ public: {} }, referrer.publicKey)
+      .accounts({
+        buyer: wallet.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        // The referral of the currency paid in
+        referral: spl ? spl_referral : sol_referral,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, wallet.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: spl ? payment_option : null,
+        bidToken: spl ? bid_token : null,
+        buyerBidTokenAccount: spl ? buyer_bidtoken_ata : null,
+        auctionVaultBidTokenAccount: spl ? auction_vault_bidtoken_ata : null,
+        bidTokenProgram: spl ? TOKEN_PROGRAM_ID : null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const claimReferralReward = async (referral: PublicKey, reward_accounts: PublicKey[]) =>
+    program.methods
+      .claimReferralReward()
+      .accounts({
+        referrer: referrer.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        proceedsDestination: sender.publicKey,
+        referral: referral,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        reward_accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: !pubkey.equals(bid_token) }))
+      )
+      .signers([referrer])
+      .rpc();
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 16, 45, [10, 13]),
+      referralBps: referral_bps,
+    }));
+    console.log("init_auc_tx", tx);
+
+    const add_token_tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", add_token_tx);
+
+    const add_payment_option_tx = await program.methods
+      .addPaymentOption({ pricePerTicket: new BN(price_per_ticket) })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        config: config,
+        paymentOption: payment_option,
+        auctionVaultBidTokenAccount: auction_vault_bidtoken_ata,
+        bidToken: bid_token,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("add_payment_option_tx", add_payment_option_tx);
+  });
+
+  it("Register the referrer in SOL and SPL!", async () => {
+    // Fund the referrer and the SOL buyer, and create their token accounts
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: referrer.publicKey,
+          lamports: LAMPORTS_PER_SOL / 20,
+        }),
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: sol_buyer.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, bid_token, referrer.publicKey);
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, sol_buyer.publicKey);
+
+    for (const [mint, referral] of [
+      [PublicKey.default, sol_referral],
+      [bid_token, spl_referral],
+    ]) {
+      const tx = await program.methods
+        .registerReferrer(mint)
+        .accounts({
+          referrer: referrer.publicKey,
+          auction: auction,
+          referral: referral,
+          config: config,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([referrer])
+        .rpc();
+      console.log("register_referrer_tx", tx);
+    }
+  });
+
+  it("Reserve the rewards of referred buys!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(18000);
+
+    console.log("buy_tx", await buy(buyer, true));
+    console.log("buy_tx", await buy(sol_buyer, false));
+
+    // The bid token reward is kept out of the proceeds of the payment option
+    const spl_reward = reward(price_per_ticket);
+    const spl_referral_data = await program.account.referral.fetch(spl_referral);
+    assert.equal(spl_referral_data.volume.toNumber(), price_per_ticket);
+    assert.equal(spl_referral_data.reward.toNumber(), spl_reward);
+    const payment_option_data = await program.account.paymentOption.fetch(payment_option);
+    assert.equal(payment_option_data.referralReserved.toNumber(), spl_reward);
+    assert.equal(payment_option_data.totalCollected.toNumber(), price_per_ticket - spl_reward);
+
+    // And the SOL reward out of the SOL proceeds
+    const sol_referral_data = await program.account.referral.fetch(sol_referral);
+    const sol_reward = reward(sol_referral_data.volume.toNumber());
+    assert.equal(sol_referral_data.reward.toNumber(), sol_reward);
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.equal(auction_data.solReferralReserved.toNumber(), sol_reward);
+    assert.equal(auction_data.solCollected.toNumber(), sol_referral_data.volume.toNumber() - sol_reward);
+  });
+
+  it("Leave the rewards in the vault when withdrawing!", async () => {
+    console.log("Waiting for Auction to End...");
+    await delay(30000);
+
+    const config_data = await program.account.config.fetch(config);
+    const destination_bidtoken_ata = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, sender, bid_token, sender.publicKey)
+    ).address;
+    const treasury_bidtoken_ata = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, sender, bid_token, config_data.feeTreasury)
+    ).address;
+
+    const tx = await program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: auction_vault_ata,
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: config_data.feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        [
+          payment_option,
+          auction_vault_bidtoken_ata,
+          destination_bidtoken_ata,
+          treasury_bidtoken_ata,
+          bid_token,
+          TOKEN_PROGRAM_ID,
+        ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: !pubkey.equals(TOKEN_PROGRAM_ID) }))
+      )
+      .signers([sender])
+      .rpc();
+    console.log("withdraw_funds_tx", tx);
+
+    // Exactly the rewards are left, on top of the vault's rent reserve
+    const spl_reward = (await program.account.referral.fetch(spl_referral)).reward.toNumber();
+    const sol_reward = (await program.account.referral.fetch(sol_referral)).reward.toNumber();
+    assert.equal(await tokenBalance(auction_vault_bidtoken_ata), spl_reward);
+    const vault_account = await provider.connection.getAccountInfo(auction_vault);
+    const rent_reserve = await provider.connection.getMinimumBalanceForRentExemption(vault_account.data.length);
+    assert.equal(vault_account.lamports - rent_reserve, sol_reward);
+  });
+
+  it("Claim the SOL reward!", async () => {
+    const expected = (await program.account.referral.fetch(sol_referral)).reward.toNumber();
+    const before = await provider.connection.getBalance(referrer.publicKey);
+    console.log("claim_referral_reward_tx", await claimReferralReward(sol_referral, []));
+
+    // The provider wallet pays the transaction, so the referrer gets exactly the reward
+    assert.equal((await provider.connection.getBalance(referrer.publicKey)) - before, expected);
+    const referral_data = await program.account.referral.fetch(sol_referral);
+    assert.equal(referral_data.reward.toNumber(), 0);
+    assert.equal(referral_data.claimed.toNumber(), expected);
+    assert.equal((await program.account.auction.fetch(auction)).solReferralReserved.toNumber(), 0);
+  });
+
+  it("Claim the SPL reward!", async () => {
+    const expected = (await program.account.referral.fetch(spl_referral)).reward.toNumber();
+    const before = await tokenBalance(referrer_bidtoken_ata);
+    const tx = await claimReferralReward(spl_referral, [
+      payment_option,
+      auction_vault_bidtoken_ata,
+      referrer_bidtoken_ata,
+      bid_token,
+    ]);
+    console.log("claim_referral_reward_tx", tx);
+
+    assert.equal((await tokenBalance(referrer_bidtoken_ata)) - before, expected);
+    assert.equal(await tokenBalance(auction_vault_bidtoken_ata), 0);
+    const referral_data = await program.account.referral.fetch(spl_referral);
+    assert.equal(referral_data.reward.toNumber(), 0);
+    assert.equal(referral_data.claimed.toNumber(), expected);
+
+    // Nothing is left to claim
+    try {
+      await claimReferralReward(spl_referral, [
+        payment_option,
+        auction_vault_bidtoken_ata,
+        referrer_bidtoken_ata,
+        bid_token,
+      ]);
+      assert.fail("referral reward was claimed twice");
+    } catch (err) {
+      assert.include(err.toString(), "NothingToClaim");
+    }
+  });
+});
@@ -0,0 +1,176 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("registration", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Never registers
+  const registered_buyer = Keypair.generate();
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+
+  const seed = (text: string) => Buffer.from(anchor.utils.bytes.utf8.encode(text));
+
+  // The auctions init_auction creates next, the open one first
+  const open = await nextAuctionPdas(program);
+  const gated = await nextAuctionPdas(program, 1);
+
+  const buyerPda = ({ auction }, wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync([seed("buyer"), wallet.toBuffer(), auction.toBuffer()], program.programId)[0];
+
+  const initRegistrationAuction = async (pdas, start_time: number, registration_required: boolean) =>
+    initAuction(program, sender, pdas, defaultAuctionParams({
+      name: registration_required ? "lampbit-auction-registered" : "lampbit-auction-open",
+      ...saleTimes(start_time, 14, 120, [8, 11]),
+      registrationRequired: registration_required,
+      // Registration closes when the pre-sale opens
+      registrationStart: new BN(registration_required ? start_time : 0),
+      registrationEnd: new BN(registration_required ? start_time + 8 : 0),
+    }));
+
+  const buy = async ({ auction, auction_vault }, wallet: Keypair) =>
+    program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: wallet.publicKey,
+        buyerPda: buyerPda({ auction }, wallet.publicKey),
+        referral: wallet.publicKey,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, wallet.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [seed("whitelist"), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [seed("stake_allocation"), auction.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  it("Init an open and a registration-only auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    console.log("init_auc_tx", await initRegistrationAuction(open, start_time, false));
+    console.log("init_auc_tx", await initRegistrationAuction(gated, start_time, true));
+    console.log("add_token_tx", await addToken(program, sender, open, auction_token));
+    console.log("add_token_tx", await addToken(program, sender, gated, auction_token));
+  });
+
+  it("Register within the registration window!", async () => {
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: registered_buyer.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, registered_buyer.publicKey);
+
+    const tx = await program.methods
+      .register()
+      .accounts({
+        buyer: registered_buyer.publicKey,
+        auction: gated.auction,
+        buyerPda: buyerPda(gated, registered_buyer.publicKey),
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([registered_buyer])
+      .rpc();
+    console.log("register_tx", tx);
+  });
+
+  it("Buy from an open auction without registering!", async () => {
+    console.log("Lets wait for the Auctions to go LIVE...");
+    await delay(16000);
+
+    const tx = await buy(open, buyer);
+    console.log("buy_tx", tx);
+
+    // The buy created the buyer account, which stays unregistered
+    const buyer_data = await program.account.buyer.fetch(buyerPda(open, buyer.publicKey));
+    assert.isTrue(buyer_data.participate);
+    assert.isTrue(buyer_data.wallet.equals(buyer.publicKey));
+    assert.isTrue(buyer_data.auction.equals(open.auction));
+    assert.equal(buyer_data.registeredAt.toNumber(), 0);
+  });
+
+  it("Reject an unregistered buyer of a registration-only auction!", async () => {
+    try {
+      await buy(gated, buyer);
+      assert.fail("unregistered buyer bought from a registration-only auction");
+    } catch (err) {
+      assert.include(err.toString(), "NotRegistered");
+    }
+  });
+
+  it("Buy from a registration-only auction once registered!", async () => {
+    const tx = await buy(gated, registered_buyer);
+    console.log("buy_tx", tx);
+
+    const buyer_data = await program.account.buyer.fetch(buyerPda(gated, registered_buyer.publicKey));
+    assert.isTrue(buyer_data.participate);
+    assert.isAbove(buyer_data.registeredAt.toNumber(), 0);
+  });
+});
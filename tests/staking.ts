@@ -0,0 +1,316 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("staking", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction, staked as the platform token too
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  // Seconds a stake is locked for
+  const lock_period = 60;
+  // Minimum stake and pre-sale tickets of each tier, best first
+  const thresholds = [300, 200, 100].map((tokens) => new BN(tokens).mul(new BN(LAMPORTS_PER_SOL)));
+  const tier_tickets = [3, 2, 1];
+  // Enough for the second tier
+  const staked = thresholds[1];
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+  const staker = Keypair.generate();
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+  const staker_token_ata = await getAssociatedTokenAddress(auction_token, staker.publicKey);
+
+  const [config, config_bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const [stake_vault, stake_vault_bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("stake_vault"))],
+    program.programId
+  );
+  const stake_vault_ata = await getAssociatedTokenAddress(auction_token, stake_vault, true);
+  const [stake, __] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("stake")), staker.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const auction_name = "lampbit-auction-staking";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const [stake_allocation, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), staker.publicKey.toBuffer()],
+    program.programId
+  );
+  const [buyer_pda, ______] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), staker.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+
+  const test_data = {
+    funding_demand: 1,
+    token_amount: 360000000,
+    unit_ticket_amount: 400000,
+  };
+
+  const tokenBalance = async (token_account: PublicKey) =>
+    new BN((await provider.connection.getTokenAccountBalance(token_account)).value.amount);
+
+  const preSaleBuy = async () =>
+    program.methods
+      .buy({ preSale: {} }, null)
+      .accounts({
+        buyer: staker.publicKey,
+        buyerPda: buyer_pda,
+        referral: staker.publicKey,
+        buyerAuctionTokenAccount: staker_token_ata,
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), staker.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: stake_allocation,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([staker])
+      .rpc();
+
+  const unstake = async (amount: BN) =>
+    program.methods
+      .unstake(amount)
+      .accounts({
+        staker: staker.publicKey,
+        stake: stake,
+        config: config,
+        stakeVault: stake_vault,
+        stakerTokenAccount: staker_token_ata,
+        stakeVaultTokenAccount: stake_vault_ata,
+        stakeMint: auction_token,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([staker])
+      .rpc();
+
+  it("Migrate Config!", async () => {
+    // Anyone can migrate the config, here the provider wallet
+    const tx = await program.methods
+      .migrateConfig()
+      .accounts({
+        payer: provider.wallet.publicKey,
+        config: config,
+        stakeVault: stake_vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    console.log("migrate_config_tx", tx);
+
+    // The config has the current layout and the bumps
+    const account = await provider.connection.getAccountInfo(config);
+    assert.isAtLeast(account.data.length, program.account.config.size);
+    const config_data = await program.account.config.fetch(config);
+    assert.equal(config_data.stakeVaultBump, stake_vault_bump);
+    assert.equal(config_data.bump, config_bump);
+  });
+
+  it("Configure Staking!", async () => {
+    // The provider wallet is the protocol admin
+    const tx = await program.methods
+      .configureStaking({
+        stakeMint: auction_token,
+        lockPeriod: new BN(lock_period),
+        thresholds: thresholds,
+        tickets: tier_tickets,
+      })
+      .accounts({
+        admin: provider.wallet.publicKey,
+        config: config,
+        stakeVault: stake_vault,
+      })
+      .rpc();
+    console.log("configure_staking_tx", tx);
+
+    const config_data = await program.account.config.fetch(config);
+    assert.isTrue(config_data.stakeMint.equals(auction_token));
+    assert.equal(config_data.stakeLockPeriod.toNumber(), lock_period);
+  });
+
+  it("Stake!", async () => {
+    // Fund the staker with SOL and the platform token
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: staker.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, staker.publicKey);
+    await transfer(provider.connection, sender, sender_auctiontoken_ata, staker_token_ata, sender, BigInt(staked.toString()));
+
+    const total_staked = (await program.account.config.fetch(config)).totalStaked;
+    const tx = await program.methods
+      .stake(staked)
+      .accounts({
+        staker: staker.publicKey,
+        stake: stake,
+        config: config,
+        stakeVault: stake_vault,
+        stakerTokenAccount: staker_token_ata,
+        stakeVaultTokenAccount: stake_vault_ata,
+        stakeMint: auction_token,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([staker])
+      .rpc();
+    console.log("stake_tx", tx);
+
+    const stake_data = await program.account.stake.fetch(stake);
+    assert.isTrue(stake_data.amount.eq(staked));
+    assert.equal((await tokenBalance(staker_token_ata)).toNumber(), 0);
+    const config_data = await program.account.config.fetch(config);
+    assert.isTrue(config_data.totalStaked.eq(total_staked.add(staked)));
+  });
+
+  it("Init Auction and snapshot the stake!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const init_tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      // Ends before the stake is unlocked, snapshot_stake only runs before the pre-sale starts
+      ...saleTimes(start_time, 30, 40, [12, 27]),
+    }));
+    console.log("init_auc_tx", init_tx);
+
+    const add_token_tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", add_token_tx);
+
+    const snapshot_tx = await program.methods
+      .snapshotStake()
+      .accounts({
+        staker: staker.publicKey,
+        auction: auction,
+        stake: stake,
+        stakeAllocation: stake_allocation,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([staker])
+      .rpc();
+    console.log("snapshot_stake_tx", snapshot_tx);
+
+    // The stake reaches the second tier
+    const allocation = await program.account.stakeAllocation.fetch(stake_allocation);
+    assert.equal(allocation.tier, 1);
+    assert.equal(allocation.tickets, tier_tickets[1]);
+    assert.equal(allocation.used, 0);
+  });
+
+  it("Buy the whole allocation in the pre-sale!", async () => {
+    console.log("Lets wait for the Pre-Sale to go LIVE...");
+    await delay(14000);
+
+    const tx = await preSaleBuy();
+    console.log("buy_tx", tx);
+
+    // Every ticket of the allocation is bought at once, without a whitelist entry
+    const tickets = tier_tickets[1];
+    const allocation = await program.account.stakeAllocation.fetch(stake_allocation);
+    assert.equal(allocation.used, tickets);
+    const buyer_data = await program.account.buyer.fetch(buyer_pda);
+    assert.isTrue(buyer_data.participate);
+    assert.equal(buyer_data.tickets, tickets);
+    const bought = new BN(test_data.unit_ticket_amount * tickets).mul(new BN(LAMPORTS_PER_SOL));
+    assert.isTrue((await tokenBalance(staker_token_ata)).eq(bought));
+  });
+
+  it("Reject buying again with the allocation!", async () => {
+    try {
+      await preSaleBuy();
+      assert.fail("staker bought twice");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyParticipated");
+    }
+  });
+
+  it("Unstake once the lock is over!", async () => {
+    try {
+      await unstake(staked);
+      assert.fail("stake was withdrawn while locked");
+    } catch (err) {
+      assert.include(err.toString(), "StakeLocked");
+    }
+
+    const stake_data = await program.account.stake.fetch(stake);
+    const wait = stake_data.lockedUntil.toNumber() - Math.floor(Date.now() / 1000) + 2;
+    console.log("Waiting for the stake to unlock...");
+    await delay(Math.max(wait, 0) * 1000);
+
+    const before = await tokenBalance(staker_token_ata);
+    const total_staked = (await program.account.config.fetch(config)).totalStaked;
+    const tx = await unstake(staked);
+    console.log("unstake_tx", tx);
+
+    assert.isTrue((await tokenBalance(staker_token_ata)).sub(before).eq(staked));
+    assert.isTrue((await program.account.stake.fetch(stake)).amount.isZero());
+    const config_data = await program.account.config.fetch(config);
+    assert.isTrue(config_data.totalStaked.eq(total_staked.sub(staked)));
+  });
+});
@@ -0,0 +1,255 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  getMintLen,
+  mintTo,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("token 2022", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner and mint authority
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Pays in the bid token
+
+  // Token-2022 mints withholding a fee on every transfer
+  const auction_token_keypair = Keypair.generate();
+  const bid_token_keypair = Keypair.generate();
+  const auction_token = auction_token_keypair.publicKey;
+  const bid_token = bid_token_keypair.publicKey;
+  const auction_token_fee_bps = 100;
+  const bid_token_fee_bps = 50;
+  // Bid token base units for one ticket
+  const price_per_ticket = 1000000;
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-token-2022";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const [payment_option, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("payment_option")), auction.toBuffer(), bid_token.toBuffer()],
+    program.programId
+  );
+
+  const tokenAccount = (mint: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddress(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+  const sender_auctiontoken_ata = await tokenAccount(auction_token, sender.publicKey);
+  const buyer_auctiontoken_ata = await tokenAccount(auction_token, buyer.publicKey);
+  const buyer_bidtoken_ata = await tokenAccount(bid_token, buyer.publicKey);
+  const auction_vault_ata = await tokenAccount(auction_token, auction_vault);
+  const auction_vault_bidtoken_ata = await tokenAccount(bid_token, auction_vault);
+
+  const test_data = {
+    token_amount: 1000,
+    unit_ticket_amount: 10,
+  };
+
+  const tokenBalance = async (token_account: PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(token_account)).value.amount);
+
+  // Fee withheld from a transfer of `amount`, rounded up like the token program
+  const transferFee = (amount: number, fee_bps: number) => Math.ceil((amount * fee_bps) / 10000);
+
+  const createMint = async (mint: Keypair, decimals: number, fee_bps: number) => {
+    const mint_len = getMintLen([ExtensionType.TransferFeeConfig]);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: sender.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space: mint_len,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mint_len),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint.publicKey,
+          sender.publicKey,
+          sender.publicKey,
+          fee_bps,
+          BigInt("18446744073709551615"),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(mint.publicKey, decimals, sender.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [sender, mint]
+    );
+  };
+
+  it("Create the Token-2022 mints!", async () => {
+    await createMint(auction_token_keypair, 9, auction_token_fee_bps);
+    await createMint(bid_token_keypair, 6, bid_token_fee_bps);
+
+    for (const [mint, owner, amount] of [
+      [auction_token, sender.publicKey, 2 * test_data.token_amount * LAMPORTS_PER_SOL],
+      [bid_token, buyer.publicKey, 10 * price_per_ticket],
+    ] as [PublicKey, PublicKey, number][]) {
+      const token_account = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        sender,
+        mint,
+        owner,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(provider.connection, sender, mint, token_account.address, sender, amount, [], undefined, TOKEN_2022_PROGRAM_ID);
+    }
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      sender,
+      auction_token,
+      buyer.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  it("Init Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 16, 600, [10, 13]),
+      payWithNative: false,
+      tokensInPool: new BN(test_data.token_amount),
+      tokenQuantityPerTicket: new BN(test_data.unit_ticket_amount),
+    }));
+    console.log("init_auc_tx", tx);
+  });
+
+  it("Deposit the whole pool despite the transfer fee!", async () => {
+    const before = await tokenBalance(sender_auctiontoken_ata);
+    const tx = await addToken(program, sender, pdas, auction_token, TOKEN_2022_PROGRAM_ID);
+    console.log("add_token_tx", tx);
+
+    // The owner pays the fee on top, so the vault holds exactly the pool
+    const pool = test_data.token_amount * LAMPORTS_PER_SOL;
+    assert.equal(await tokenBalance(auction_vault_ata), pool);
+    assert.isAbove(before - (await tokenBalance(sender_auctiontoken_ata)), pool);
+
+    const add_payment_option_tx = await program.methods
+      .addPaymentOption({ pricePerTicket: new BN(price_per_ticket) })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        config: config,
+        paymentOption: payment_option,
+        auctionVaultBidTokenAccount: auction_vault_bidtoken_ata,
+        bidToken: bid_token,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("add_payment_option_tx", add_payment_option_tx);
+  });
+
+  it("Collect only what the vault receives of a bid!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(18000);
+
+    const buyer_bid_before = await tokenBalance(buyer_bidtoken_ata);
+    const buyer_tokens_before = await tokenBalance(buyer_auctiontoken_ata);
+
+    const tx = await program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: buyer.publicKey,
+        buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: auction_vault_ata,
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: payment_option,
+        bidToken: bid_token,
+        buyerBidTokenAccount: buyer_bidtoken_ata,
+        auctionVaultBidTokenAccount: auction_vault_bidtoken_ata,
+        bidTokenProgram: TOKEN_2022_PROGRAM_ID,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("buy_tx", tx);
+
+    // The buyer pays the ticket price and the vault receives it less the bid token fee
+    const received = price_per_ticket - transferFee(price_per_ticket, bid_token_fee_bps);
+    assert.equal(buyer_bid_before - (await tokenBalance(buyer_bidtoken_ata)), price_per_ticket);
+    assert.equal(await tokenBalance(auction_vault_bidtoken_ata), received);
+    const payment_option_data = await program.account.paymentOption.fetch(payment_option);
+    assert.equal(payment_option_data.totalCollected.toNumber(), received);
+
+    // The ticket leaves the vault in full and reaches the buyer less the auction token fee
+    const ticket = test_data.unit_ticket_amount * LAMPORTS_PER_SOL;
+    const pool = test_data.token_amount * LAMPORTS_PER_SOL;
+    assert.equal(await tokenBalance(auction_vault_ata), pool - ticket);
+    assert.equal(
+      (await tokenBalance(buyer_auctiontoken_ata)) - buyer_tokens_before,
+      ticket - transferFee(ticket, auction_token_fee_bps)
+    );
+  });
+});
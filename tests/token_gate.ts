@@ -0,0 +1,317 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  createMint,
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transfer,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Metaplex, keypairIdentity } from "@metaplex-foundation/js";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("token gate", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Token whose balance gates the pre-sale of the SPL gated auction
+  const gate_token = new PublicKey("6YMTJpgraqrd68mBfjkwG65FPuHiZWuifi4UP1WUoHjK");
+  const gate_min_amount = 1000000;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner, and the authority of the NFT collection
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Holds the gating NFT and enough gate tokens
+  const buyer_gatetoken_ata = new PublicKey("5Q3NSjAYBFNyWL6sJkiz7YidYpBqgxrYTK5nnrfkzFcR");
+  const other = Keypair.generate(); // Holds neither until the NFT is passed on
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+
+  const seed = (text: string) => Buffer.from(anchor.utils.bytes.utf8.encode(text));
+
+  // The auctions init_auction creates next, the NFT gated one first
+  const nft_gated = await nextAuctionPdas(program);
+  const spl_gated = await nextAuctionPdas(program, 1);
+  const token_2022_gated = await nextAuctionPdas(program, 2);
+
+  const metaplex = Metaplex.make(provider.connection).use(keypairIdentity(sender));
+  let collection_mint: PublicKey;
+  let nft_mint: PublicKey;
+  let nft_metadata: PublicKey;
+  let token_2022_gate_token: PublicKey;
+
+  const gateMarker = ({ auction }, mint: PublicKey) =>
+    PublicKey.findProgramAddressSync([seed("gate_marker"), auction.toBuffer(), mint.toBuffer()], program.programId)[0];
+
+  const initGatedAuction = async (pdas, start_time: number, name: string, gate) =>
+    initAuction(program, sender, pdas, defaultAuctionParams({
+      name,
+      ...saleTimes(start_time, 60, 120, [10, 55]),
+      ...gate,
+    }));
+
+  // Pre-sale buy without a whitelist entry, eligible by the gate accounts only
+  const preSaleBuy = async ({ auction, auction_vault }, wallet: Keypair, gate_accounts: PublicKey[]) =>
+    program.methods
+      .buy({ preSale: {} }, null)
+      .accounts({
+        buyer: wallet.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [seed("buyer"), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: wallet.publicKey,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, wallet.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [seed("whitelist"), wallet.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [seed("stake_allocation"), auction.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        // The marker PDA is created by the buy, so it is writable
+        gate_accounts.map((pubkey, index) => ({ pubkey, isSigner: false, isWritable: index == 2 }))
+      )
+      .signers([wallet])
+      .rpc();
+
+  it("Mint a gating NFT of a verified collection!", async () => {
+    const { nft: collection } = await metaplex.nfts().create({
+      name: "Lampbit Gate",
+      uri: "https://lampbit.io/gate.json",
+      sellerFeeBasisPoints: 0,
+      isCollection: true,
+    });
+    collection_mint = collection.address;
+
+    const { nft } = await metaplex.nfts().create({
+      name: "Lampbit Gate #1",
+      uri: "https://lampbit.io/gate-1.json",
+      sellerFeeBasisPoints: 0,
+      collection: collection_mint,
+      tokenOwner: buyer.publicKey,
+    });
+    await metaplex.nfts().verifyCollection({ mintAddress: nft.address, collectionMintAddress: collection_mint });
+    nft_mint = nft.address;
+    nft_metadata = nft.metadataAddress;
+    console.log("nft_mint:", nft_mint.toString());
+
+    // Fund the other wallet and create its token accounts
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: sender.publicKey,
+          toPubkey: other.publicKey,
+          lamports: LAMPORTS_PER_SOL / 10,
+        })
+      ),
+      [sender]
+    );
+    for (const mint of [auction_token, gate_token, nft_mint]) {
+      await getOrCreateAssociatedTokenAccount(provider.connection, sender, mint, other.publicKey);
+    }
+  });
+
+  it("Mint a Token-2022 gate token!", async () => {
+    token_2022_gate_token = await createMint(
+      provider.connection,
+      sender,
+      sender.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const buyer_token_account = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      sender,
+      token_2022_gate_token,
+      buyer.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      sender,
+      token_2022_gate_token,
+      buyer_token_account.address,
+      sender,
+      gate_min_amount,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  it("Init the gated auctions!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    console.log(
+      "init_auc_tx",
+      await initGatedAuction(nft_gated, start_time, "lampbit-auction-nft-gate", {
+        gateKind: { nftCollection: {} },
+        gateMint: collection_mint,
+        gateMinAmount: new BN(0),
+      })
+    );
+    console.log(
+      "init_auc_tx",
+      await initGatedAuction(spl_gated, start_time, "lampbit-auction-spl-gate", {
+        gateKind: { splBalance: {} },
+        gateMint: gate_token,
+        gateMinAmount: new BN(gate_min_amount),
+      })
+    );
+    console.log(
+      "init_auc_tx",
+      await initGatedAuction(token_2022_gated, start_time, "lampbit-auction-token-2022-gate", {
+        gateKind: { splBalance: {} },
+        gateMint: token_2022_gate_token,
+        gateMinAmount: new BN(gate_min_amount),
+      })
+    );
+    console.log("add_token_tx", await addToken(program, sender, nft_gated, auction_token));
+    console.log("add_token_tx", await addToken(program, sender, spl_gated, auction_token));
+    console.log("add_token_tx", await addToken(program, sender, token_2022_gated, auction_token));
+  });
+
+  it("Buy in the pre-sale with a collection NFT!", async () => {
+    console.log("Lets wait for the Pre-Sales to go LIVE...");
+    await delay(12000);
+
+    const marker = gateMarker(nft_gated, nft_mint);
+    const tx = await preSaleBuy(nft_gated, buyer, [
+      await getAssociatedTokenAddress(nft_mint, buyer.publicKey),
+      nft_metadata,
+      marker,
+    ]);
+    console.log("buy_tx", tx);
+
+    // The NFT is marked as used by the buyer in this auction
+    const marker_data = await program.account.gateMarker.fetch(marker);
+    assert.isTrue(marker_data.auction.equals(nft_gated.auction));
+    assert.isTrue(marker_data.mint.equals(nft_mint));
+    assert.isTrue(marker_data.buyer.equals(buyer.publicKey));
+  });
+
+  it("Reject the same NFT from another wallet!", async () => {
+    const other_nft_ata = await getAssociatedTokenAddress(nft_mint, other.publicKey);
+    await transfer(
+      provider.connection,
+      sender,
+      await getAssociatedTokenAddress(nft_mint, buyer.publicKey),
+      other_nft_ata,
+      buyer,
+      1
+    );
+
+    try {
+      await preSaleBuy(nft_gated, other, [other_nft_ata, nft_metadata, gateMarker(nft_gated, nft_mint)]);
+      assert.fail("one NFT admitted two wallets");
+    } catch (err) {
+      assert.include(err.toString(), "GateTokenUsed");
+    }
+  });
+
+  it("Buy in the pre-sale with a gating balance!", async () => {
+    const tx = await preSaleBuy(spl_gated, buyer, [buyer_gatetoken_ata]);
+    console.log("buy_tx", tx);
+
+    const buyer_data = await program.account.buyer.fetch(
+      PublicKey.findProgramAddressSync(
+        [seed("buyer"), buyer.publicKey.toBuffer(), spl_gated.auction.toBuffer()],
+        program.programId
+      )[0]
+    );
+    assert.isTrue(buyer_data.participate);
+  });
+
+  it("Buy in the pre-sale with a Token-2022 gating balance!", async () => {
+    const tx = await preSaleBuy(token_2022_gated, buyer, [
+      await getAssociatedTokenAddress(token_2022_gate_token, buyer.publicKey, false, TOKEN_2022_PROGRAM_ID),
+    ]);
+    console.log("buy_tx", tx);
+
+    const buyer_data = await program.account.buyer.fetch(
+      PublicKey.findProgramAddressSync(
+        [seed("buyer"), buyer.publicKey.toBuffer(), token_2022_gated.auction.toBuffer()],
+        program.programId
+      )[0]
+    );
+    assert.isTrue(buyer_data.participate);
+  });
+
+  it("Reject a balance under the gate minimum!", async () => {
+    // Gate tokens short of the minimum by one base unit
+    const other_gatetoken_ata = await getAssociatedTokenAddress(gate_token, other.publicKey);
+    await transfer(provider.connection, sender, buyer_gatetoken_ata, other_gatetoken_ata, buyer, gate_min_amount - 1);
+
+    try {
+      await preSaleBuy(spl_gated, other, [other_gatetoken_ata]);
+      assert.fail("a balance under the minimum was accepted");
+    } catch (err) {
+      assert.include(err.toString(), "NotWhitelisted");
+    }
+
+    // Nor someone else's token account
+    try {
+      await preSaleBuy(spl_gated, other, [buyer_gatetoken_ata]);
+      assert.fail("another wallet's balance was accepted");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidGateAccounts");
+    }
+  });
+});
@@ -0,0 +1,136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  SYSVAR_CLOCK_PUBKEY,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getMint,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("unsold token policy", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-burn";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+
+  const test_data = {
+    token_amount: 360000000,
+  };
+
+  const initUnsoldAuction = async (policy) => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    return initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 8, 12, [4, 6]),
+      unsoldTokenPolicy: policy,
+    }));
+  };
+
+  it("Reject TransferTo without a treasury!", async () => {
+    try {
+      await initUnsoldAuction({ transferTo: {} });
+      assert.fail("auction was created without a treasury for its unsold tokens");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidUnsoldTokenPolicy");
+    }
+  });
+
+  it("Init Auction!", async () => {
+    const tx = await initUnsoldAuction({ burn: {} });
+    console.log("init_auc_tx", tx);
+
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.deepEqual(auction_data.unsoldTokenPolicy, { burn: {} });
+  });
+
+  it("Add Token!", async () => {
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Withdraw Funds burns the unsold tokens!", async () => {
+    console.log("Lets wait for Auction to end...");
+    await delay(14000);
+
+    const settled = new Promise<any>((resolve) => {
+      const listener = program.addEventListener("UnsoldTokensSettled", (event) => {
+        program.removeEventListener(listener);
+        resolve(event);
+      });
+    });
+    const supply_before = (await getMint(provider.connection, auction_token)).supply;
+
+    const tx = await program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: auction_vault_ata,
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("withdraw_funds_tx", tx);
+
+    // Nothing was sold, so the whole pool is burnt (add_token deposits it in base units)
+    const pool_amount = BigInt(test_data.token_amount) * BigInt(LAMPORTS_PER_SOL);
+    const supply_after = (await getMint(provider.connection, auction_token)).supply;
+    assert.equal(supply_before - supply_after, pool_amount);
+
+    const event = await settled;
+    assert.deepEqual(event.policy, { burn: {} });
+    assert.equal(event.amount.toString(), pool_amount.toString());
+    assert.isTrue(event.tokenAccount.equals(PublicKey.default));
+  });
+});
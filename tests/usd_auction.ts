@@ -0,0 +1,188 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+// Prices come from a mock feed, which needs a program built with the `mock-oracle` feature:
+// anchor build -- --features mock-oracle
+describe("usd denominated auction", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner and the mock price feed authority
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  );
+
+  const buyer_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, buyer.publicKey);
+
+  const [config, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-usd";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction, auction_vault } = pdas;
+  const auction_vault_ata = await getAssociatedTokenAddress(auction_token, auction_vault, true);
+  const [buyer_pda, ___] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [whitelist_pda, ______] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+    program.programId
+  );
+  const [mock_price_feed, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("mock_price_feed")), sender.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const test_data = {
+    token_amount: 360000000,
+    unit_ticket_amount: 400000,
+    usd_ticket_price: 216_000_000, // $216 with 6 decimals
+    sol_usd_price: 10_800_000_000, // $108 with expo -8
+    sol_usd_expo: -8,
+  };
+
+  it("Set Mock Price!", async () => {
+    const tx = await program.methods
+      .setMockPrice({
+        price: new BN(test_data.sol_usd_price),
+        conf: new BN(1_000_000),
+        expo: test_data.sol_usd_expo,
+      })
+      .accounts({
+        authority: sender.publicKey,
+        mockPriceFeed: mock_price_feed,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("set_mock_price_tx", tx);
+  });
+
+  it("Init USD Auction!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(start_time, 7, 30, [0, 5]),
+      fundingDemand: new BN(0),
+      usdDenominated: true,
+      usdTicketPrice: new BN(test_data.usd_ticket_price),
+      priceFeed: mock_price_feed,
+      maxPriceAge: new BN(60),
+      maxPriceConfidenceBps: 100,
+    }));
+    console.log("init_auc_tx", tx);
+  });
+
+  it("Add Token!", async () => {
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Register!", async () => {
+    const tx = await program.methods
+      .register()
+      .accounts({
+        buyer: buyer.publicKey,
+        auction: auction,
+        buyerPda: buyer_pda,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("register_tx", tx);
+  });
+
+  it("Buy Tokens using Sol at the mock SOL/USD price!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(7000);
+
+    // Refresh the quote so it is within maxPriceAge
+    await program.methods
+      .setMockPrice({
+        price: new BN(test_data.sol_usd_price),
+        conf: new BN(1_000_000),
+        expo: test_data.sol_usd_expo,
+      })
+      .accounts({
+        authority: sender.publicKey,
+        mockPriceFeed: mock_price_feed,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+    const tx = await program.methods
+      .buy({ public: {} }, null)
+      .accounts({
+        buyer: buyer.publicKey,
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        buyerPda: buyer_pda,
+        referral: buyer.publicKey,
+        whitelistPda: whitelist_pda,
+        auctionVaultTokenAccount: auction_vault_ata,
+        buyerAuctionTokenAccount: buyer_auctiontoken_ata,
+        auctionToken: auction_token,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: mock_price_feed,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    console.log("buy_tx", tx);
+
+    // $216 at $108 per SOL is 2 SOL
+    const auction_data = await program.account.auction.fetch(auction);
+    assert.equal(auction_data.solCollected.toNumber(), 2 * LAMPORTS_PER_SOL);
+  });
+});
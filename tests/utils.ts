@@ -1,6 +1,19 @@
-import { PublicKey, Keypair, Connection } from "@solana/web3.js"
-import { createAssociatedTokenAccountInstruction } from "@solana/spl-token";
-import { web3 } from "@coral-xyz/anchor";
+import {
+    PublicKey,
+    Keypair,
+    Connection,
+    SystemProgram,
+    SYSVAR_RENT_PUBKEY,
+    SYSVAR_CLOCK_PUBKEY,
+} from "@solana/web3.js"
+import {
+    createAssociatedTokenAccountInstruction,
+    getAssociatedTokenAddress,
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { web3, BN, Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
 
 const connection = new Connection("https://api.devnet.solana.com")
 
@@ -23,4 +36,141 @@ export async function createATA(
         [payer]
     );
     console.log("ATA Creation Tnx Sig:", tnx_sig)
-}
\ No newline at end of file
+}
+
+// PDA of the program's global config
+export function configPda(program: Program<Launchpad>) {
+    return PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0]
+}
+
+// PDAs of the auction with the given id
+export function auctionPdas(program: Program<Launchpad>, auction_id: BN) {
+    const auction = PublicKey.findProgramAddressSync(
+        [Buffer.from("auction"), auction_id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+    )[0];
+    return {
+        auction,
+        auction_vault: PublicKey.findProgramAddressSync(
+            [Buffer.from("auction_vault"), auction.toBuffer()],
+            program.programId
+        )[0],
+        metadata: PublicKey.findProgramAddressSync(
+            [Buffer.from("metadata"), auction.toBuffer()],
+            program.programId
+        )[0],
+    };
+}
+
+// PDAs of the auction init_auction creates next, `offset` auctions ahead of the config's count
+export async function nextAuctionPdas(program: Program<Launchpad>, offset = 0) {
+    const auction_count = (await program.account.config.fetch(configPda(program))).auctionCount;
+    return auctionPdas(program, auction_count.addn(offset));
+}
+
+// Sale times in seconds from `now`, with an optional pre-sale. add_token only takes the
+// tokens before a pre-sale starts, so a sale opening with one leaves time to deposit first
+export function saleTimes(now: number, start: number, end: number, pre_sale?: [number, number]) {
+    return {
+        startTime: new BN(now + start),
+        endTime: new BN(now + end),
+        preSale: pre_sale !== undefined,
+        preSaleStartTime: new BN(pre_sale ? now + pre_sale[0] : 0),
+        preSaleEndTime: new BN(pre_sale ? now + pre_sale[1] : 0),
+    };
+}
+
+// init_auction params of a plain SOL sale, every optional feature off unless overridden
+export function defaultAuctionParams(overrides = {}) {
+    return {
+        name: "lampbit-auction",
+        enabled: true,
+        fixedAmount: true,
+        ...saleTimes(Math.floor(Date.now() / 1000), 600, 1200),
+        payWithNative: true,
+        tokensInPool: new BN(360000000),
+        tokenQuantityPerTicket: new BN(400000),
+        fundingDemand: new BN(1),
+        usdDenominated: false,
+        usdTicketPrice: new BN(0),
+        priceFeed: PublicKey.default,
+        maxPriceAge: new BN(0),
+        maxPriceConfidenceBps: 0,
+        tierDelays: [0, 0, 0, 0],
+        maxBuysPerSlot: 0,
+        registrationRequired: false,
+        registrationStart: new BN(0),
+        registrationEnd: new BN(0),
+        kycAuthority: PublicKey.default,
+        gateKind: { none: {} },
+        gateMint: PublicKey.default,
+        gateMinAmount: new BN(0),
+        gateTier: 0,
+        referralBps: 0,
+        liquidityBps: 0,
+        liquidityTokens: new BN(0),
+        lpUnlockTime: new BN(0),
+        proceedsDestination: PublicKey.default,
+        unsoldDestination: PublicKey.default,
+        gracePeriod: new BN(0),
+        unsoldTokenPolicy: { return: {} },
+        ...overrides,
+    };
+}
+
+// Create the auction at `pdas` owned by `owner`
+export async function initAuction(
+    program: Program<Launchpad>,
+    owner: Keypair,
+    pdas: { auction: PublicKey; auction_vault: PublicKey; metadata: PublicKey },
+    params
+) {
+    const config = configPda(program);
+    return program.methods
+        .initAuction(params)
+        .accounts({
+            owner: owner.publicKey,
+            auction: pdas.auction,
+            auctionVault: pdas.auction_vault,
+            metadata: pdas.metadata,
+            config: config,
+            feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+            rent: SYSVAR_RENT_PUBKEY,
+            systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+}
+
+// Deposit the auction's pool of `auction_token` from the owner's associated token account
+export async function addToken(
+    program: Program<Launchpad>,
+    owner: Keypair,
+    pdas: { auction: PublicKey; auction_vault: PublicKey },
+    auction_token: PublicKey,
+    token_program = TOKEN_PROGRAM_ID
+) {
+    const config = configPda(program);
+    return program.methods
+        .addToken()
+        .accounts({
+            owner: owner.publicKey,
+            auction: pdas.auction,
+            config: config,
+            auctionVault: pdas.auction_vault,
+            ownerAuctionTokenAccount: await getAssociatedTokenAddress(
+                auction_token, owner.publicKey, false, token_program
+            ),
+            auctionVaultTokenAccount: await getAssociatedTokenAddress(
+                auction_token, pdas.auction_vault, true, token_program
+            ),
+            auctionToken: auction_token,
+            rent: SYSVAR_RENT_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: token_program,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([owner])
+        .rpc();
+}
@@ -0,0 +1,250 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("vault", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Lamports sent to the vaults outside of buys
+  const stray_lamports = LAMPORTS_PER_SOL / 100;
+  // Share of referred purchases rewarded to the referrer
+  const referral_bps = 1000;
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the auction owner and the proceeds destination
+  const buyer = Keypair.generate();
+  const referrer = Keypair.generate();
+
+  const sender_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, sender.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+
+  // The first auction owes nothing after settling and is closed, the second keeps a referral reward
+  const auctions = [await nextAuctionPdas(program), await nextAuctionPdas(program, 1)];
+  const [closed, retained] = auctions;
+  const sol_referral = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(anchor.utils.bytes.utf8.encode("referral")),
+      retained.auction.toBuffer(),
+      referrer.publicKey.toBuffer(),
+      PublicKey.default.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const rentReserve = async (auction_vault: PublicKey) =>
+    provider.connection.getMinimumBalanceForRentExemption(
+      (await provider.connection.getAccountInfo(auction_vault)).data.length
+    );
+
+  const buy = async (auction: PublicKey, auction_vault: PublicKey, referred: boolean) =>
+    program.methods
+      .buy({ public: {} }, referred ? referrer.publicKey : null)
+      .accounts({
+        buyer: buyer.publicKey,
+        buyerPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("buyer")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        referral: referred ? sol_referral : buyer.publicKey,
+        buyerAuctionTokenAccount: await getAssociatedTokenAddress(auction_token, buyer.publicKey),
+        auction: auction,
+        config: config,
+        auctionVault: auction_vault,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        auctionToken: auction_token,
+        whitelistPda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("whitelist")), buyer.publicKey.toBuffer(), auction.toBuffer()],
+          program.programId
+        )[0],
+        stakeAllocation: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("stake_allocation")), auction.toBuffer(), buyer.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        priceFeed: PublicKey.default,
+        paymentOption: null,
+        bidToken: null,
+        buyerBidTokenAccount: null,
+        auctionVaultBidTokenAccount: null,
+        bidTokenProgram: null,
+        buyerRegistry: null,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+  const withdrawFunds = async (auction: PublicKey, auction_vault: PublicKey) =>
+    program.methods
+      .withdrawFunds()
+      .accounts({
+        creator: sender.publicKey,
+        auction: auction,
+        auctionVault: auction_vault,
+        delegatePda: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("delegate")), auction.toBuffer(), sender.publicKey.toBuffer(), Buffer.from([2])],
+          program.programId
+        )[0],
+        destination: sender.publicKey,
+        auctionVaultTokenAccount: await getAssociatedTokenAddress(auction_token, auction_vault, true),
+        creatorAuctionTokenAccount: sender_auctiontoken_ata,
+        auctionToken: auction_token,
+        config: config,
+        feeTreasury: (await program.account.config.fetch(config)).feeTreasury,
+        escrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("escrow")), auction.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sender])
+      .rpc();
+
+  // Expected SOL payout of a settlement: the proceeds less the auction's fee
+  const proceeds = async (auction: PublicKey) => {
+    const auction_data = await program.account.auction.fetch(auction);
+    const sol_collected = auction_data.solCollected.toNumber();
+    return sol_collected - Math.floor((sol_collected * auction_data.feeBps) / 10000);
+  };
+
+  it("Init Auctions!", async () => {
+    const start_time = Math.floor(Date.now() / 1000);
+
+    for (const [index, pdas] of auctions.entries()) {
+      const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+        name: "lampbit-auction-vault-" + index,
+        ...saleTimes(start_time, 16, 40, [10, 13]),
+        referralBps: pdas == retained ? referral_bps : 0,
+      }));
+      console.log("init_auc_tx", tx);
+
+      const add_token_tx = await addToken(program, sender, pdas, auction_token);
+      console.log("add_token_tx", add_token_tx);
+    }
+
+    // Fund the buyer and the referrer, and send stray lamports to both vaults
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: sender.publicKey, toPubkey: buyer.publicKey, lamports: LAMPORTS_PER_SOL / 5 }),
+        SystemProgram.transfer({ fromPubkey: sender.publicKey, toPubkey: referrer.publicKey, lamports: LAMPORTS_PER_SOL / 20 }),
+        ...auctions.map(({ auction_vault }) =>
+          SystemProgram.transfer({ fromPubkey: sender.publicKey, toPubkey: auction_vault, lamports: stray_lamports })
+        )
+      ),
+      [sender]
+    );
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, buyer.publicKey);
+
+    const register_referrer_tx = await program.methods
+      .registerReferrer(PublicKey.default)
+      .accounts({
+        referrer: referrer.publicKey,
+        auction: retained.auction,
+        referral: sol_referral,
+        config: config,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([referrer])
+      .rpc();
+    console.log("register_referrer_tx", register_referrer_tx);
+  });
+
+  it("Buy in both auctions!", async () => {
+    console.log("Lets wait for Auction to go LIVE...");
+    await delay(18000);
+
+    console.log("buy_tx", await buy(closed.auction, closed.auction_vault, false));
+    console.log("buy_tx", await buy(retained.auction, retained.auction_vault, true));
+  });
+
+  it("Sweep and close the vault when nothing is owed!", async () => {
+    console.log("Waiting for Auction to End...");
+    await delay(26000);
+
+    // The proceeds, the stray lamports and the rent reserve all go to the destination
+    const expected = await proceeds(closed.auction);
+    const auction_data = await program.account.auction.fetch(closed.auction);
+    const vault_lamports = await provider.connection.getBalance(closed.auction_vault);
+    const rent_reserve = await rentReserve(closed.auction_vault);
+    assert.equal(vault_lamports, rent_reserve + auction_data.solCollected.toNumber() + stray_lamports);
+
+    const before = await provider.connection.getBalance(sender.publicKey);
+    console.log("withdraw_funds_tx", await withdrawFunds(closed.auction, closed.auction_vault));
+
+    assert.equal((await provider.connection.getBalance(sender.publicKey)) - before, expected + stray_lamports + rent_reserve);
+    assert.isNull(await provider.connection.getAccountInfo(closed.auction_vault));
+  });
+
+  it("Keep the vault rent-exempt while a reward is owed, then close it!", async () => {
+    const expected = await proceeds(retained.auction);
+    const reward = (await program.account.referral.fetch(sol_referral)).reward.toNumber();
+    assert.isAbove(reward, 0);
+
+    // Only the stray lamports are swept on top of the proceeds
+    const before = await provider.connection.getBalance(sender.publicKey);
+    console.log("withdraw_funds_tx", await withdrawFunds(retained.auction, retained.auction_vault));
+    assert.equal((await provider.connection.getBalance(sender.publicKey)) - before, expected + stray_lamports);
+
+    const rent_reserve = await rentReserve(retained.auction_vault);
+    assert.equal(await provider.connection.getBalance(retained.auction_vault), rent_reserve + reward);
+
+    // Paying the last reward out closes the vault, its rent reserve goes to the proceeds destination
+    const destination_before = await provider.connection.getBalance(sender.publicKey);
+    const tx = await program.methods
+      .claimReferralReward()
+      .accounts({
+        referrer: referrer.publicKey,
+        auction: retained.auction,
+        auctionVault: retained.auction_vault,
+        proceedsDestination: sender.publicKey,
+        referral: sol_referral,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([referrer])
+      .rpc();
+    console.log("claim_referral_reward_tx", tx);
+
+    assert.equal((await provider.connection.getBalance(sender.publicKey)) - destination_before, rent_reserve);
+    assert.isNull(await provider.connection.getAccountInfo(retained.auction_vault));
+    assert.equal((await program.account.auction.fetch(retained.auction)).solReferralReserved.toNumber(), 0);
+  });
+});
@@ -0,0 +1,295 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Launchpad } from "../target/types/launchpad";
+import { BN } from "bn.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
+} from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import fs from "fs";
+import { assert } from "chai";
+import { addToken, defaultAuctionParams, initAuction, nextAuctionPdas, saleTimes } from "./utils";
+
+describe("vesting", async () => {
+  // Configure the client to use the devnet cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Launchpad as Program<Launchpad>;
+  console.log("programId:", program.programId.toString());
+
+  // a function to set timeout or sleep
+  const delay = (ms) => new Promise((res) => setTimeout(res, ms));
+
+  // Token that the auction owner sells during Auction
+  const auction_token = new PublicKey("8CSvK7xceqUeqRaPr91r5kgteXGcWmBL48aoUQCtdizq");
+  // Another mint the owner holds, not on sale
+  const bid_token = new PublicKey("6YMTJpgraqrd68mBfjkwG65FPuHiZWuifi4UP1WUoHjK");
+
+  const sender = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/auction_owner_wallet.json", "utf-8")))
+  ); // This sender is the first auction owner and beneficiary
+  const buyer = Keypair.fromSecretKey(
+    Buffer.from(JSON.parse(fs.readFileSync("./test_wallets/buyer_wallet.json", "utf-8")))
+  ); // Not the beneficiary
+  const new_owner = Keypair.generate(); // Takes the auction and the team allocation over
+
+  const new_owner_auctiontoken_ata = await getAssociatedTokenAddress(auction_token, new_owner.publicKey);
+
+  const [config, _] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("config"))],
+    program.programId
+  );
+  const auction_name = "lampbit-auction-vesting";
+  // The auction init_auction creates next
+  const pdas = await nextAuctionPdas(program);
+  const { auction } = pdas;
+  const [team_vesting, ____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("team_vesting")), auction.toBuffer()],
+    program.programId
+  );
+  const [team_vault, _____] = PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("team_vault")), auction.toBuffer()],
+    program.programId
+  );
+  const team_vault_ata = await getAssociatedTokenAddress(auction_token, team_vault, true);
+
+  const test_data = {
+    team_amount: 100,
+  };
+
+  // Sale and vesting schedule, set when the auction is created
+  const schedule = {
+    start_time: 0,
+    end_time: 0,
+    cliff_time: 0,
+    vesting_end_time: 0,
+  };
+
+  const tokenBalance = async (token_account: PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(token_account)).value.amount);
+
+  const lockTeamAllocation = async (cliff_time: number, end_time: number, mint = auction_token) =>
+    program.methods
+      .lockTeamAllocation({
+        amount: new BN(test_data.team_amount),
+        cliffTime: new BN(cliff_time),
+        endTime: new BN(end_time),
+      })
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        teamVesting: team_vesting,
+        teamVault: team_vault,
+        ownerAuctionTokenAccount: await getAssociatedTokenAddress(mint, sender.publicKey),
+        teamVaultTokenAccount: await getAssociatedTokenAddress(mint, team_vault, true),
+        auctionToken: mint,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([sender])
+      .rpc();
+
+  const claimVestedTokens = async (beneficiary: Keypair) =>
+    program.methods
+      .claimVestedTokens()
+      .accounts({
+        beneficiary: beneficiary.publicKey,
+        teamVesting: team_vesting,
+        teamVault: team_vault,
+        teamVaultTokenAccount: team_vault_ata,
+        beneficiaryTokenAccount: await getAssociatedTokenAddress(auction_token, beneficiary.publicKey),
+        auctionToken: auction_token,
+        config: config,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([beneficiary])
+      .rpc();
+
+  it("Init Auction!", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    schedule.start_time = now + 20;
+    schedule.end_time = now + 30;
+    schedule.cliff_time = schedule.end_time + 10;
+    schedule.vesting_end_time = schedule.end_time + 40;
+
+    const tx = await initAuction(program, sender, pdas, defaultAuctionParams({
+      name: auction_name,
+      ...saleTimes(now, 20, 30, [15, 18]),
+    }));
+    console.log("init_auc_tx", tx);
+  });
+
+  it("Reject locking before the tokens on sale are added!", async () => {
+    try {
+      await lockTeamAllocation(schedule.cliff_time, schedule.vesting_end_time);
+      assert.fail("the team allocation was locked before the auction had a mint");
+    } catch (err) {
+      assert.include(err.toString(), "ConstraintAddress");
+    }
+
+    const tx = await addToken(program, sender, pdas, auction_token);
+    console.log("add_token_tx", tx);
+  });
+
+  it("Reject locking a mint that is not on sale!", async () => {
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, bid_token, sender.publicKey);
+
+    try {
+      await lockTeamAllocation(schedule.cliff_time, schedule.vesting_end_time, bid_token);
+      assert.fail("the team allocation was locked in a mint that is not on sale");
+    } catch (err) {
+      assert.include(err.toString(), "ConstraintAddress");
+    }
+  });
+
+  it("Reject a cliff before the end of the sale!", async () => {
+    try {
+      await lockTeamAllocation(schedule.end_time - 1, schedule.vesting_end_time);
+      assert.fail("a cliff inside the sale was accepted");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidVestingSchedule");
+    }
+  });
+
+  it("Lock the team allocation!", async () => {
+    const tx = await lockTeamAllocation(schedule.cliff_time, schedule.vesting_end_time);
+    console.log("lock_team_allocation_tx", tx);
+
+    // Vesting starts when the sale ends
+    const total = test_data.team_amount * LAMPORTS_PER_SOL;
+    const vesting_data = await program.account.teamVesting.fetch(team_vesting);
+    assert.isTrue(vesting_data.beneficiary.equals(sender.publicKey));
+    assert.equal(vesting_data.total.toNumber(), total);
+    assert.equal(vesting_data.claimed.toNumber(), 0);
+    assert.equal(vesting_data.startTime.toNumber(), schedule.end_time);
+    assert.equal(vesting_data.cliffTime.toNumber(), schedule.cliff_time);
+    assert.equal(vesting_data.endTime.toNumber(), schedule.vesting_end_time);
+    assert.equal(await tokenBalance(team_vault_ata), total);
+  });
+
+  it("Reject claims before the cliff!", async () => {
+    try {
+      await claimVestedTokens(sender);
+      assert.fail("tokens were claimed before the cliff");
+    } catch (err) {
+      assert.include(err.toString(), "NothingToClaim");
+    }
+  });
+
+  it("Reject claims by anyone but the beneficiary!", async () => {
+    await getOrCreateAssociatedTokenAccount(provider.connection, buyer, auction_token, buyer.publicKey);
+
+    try {
+      await claimVestedTokens(buyer);
+      assert.fail("tokens were claimed by a wallet that is not the beneficiary");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+  });
+
+  const acceptOwner = async (team_vesting_account: PublicKey | null) =>
+    program.methods
+      .acceptOwner()
+      .accounts({
+        newOwner: new_owner.publicKey,
+        auction: auction,
+        teamVesting: team_vesting_account,
+        config: config,
+      })
+      .signers([new_owner])
+      .rpc();
+
+  it("Hand the team allocation over with the auction!", async () => {
+    const propose_tx = await program.methods
+      .proposeOwner(new_owner.publicKey)
+      .accounts({
+        owner: sender.publicKey,
+        auction: auction,
+        config: config,
+      })
+      .signers([sender])
+      .rpc();
+    console.log("propose_owner_tx", propose_tx);
+
+    // The team vesting can't be left behind with the previous owner
+    try {
+      await acceptOwner(null);
+      assert.fail("ownership was accepted without the team vesting");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidTeamVesting");
+    }
+
+    const tx = await acceptOwner(team_vesting);
+    console.log("accept_owner_tx", tx);
+    assert.isTrue((await program.account.teamVesting.fetch(team_vesting)).beneficiary.equals(new_owner.publicKey));
+    await getOrCreateAssociatedTokenAccount(provider.connection, sender, auction_token, new_owner.publicKey);
+
+    // The previous owner can no longer claim
+    try {
+      await claimVestedTokens(sender);
+      assert.fail("tokens were claimed by the previous owner");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+  });
+
+  it("Claim the linearly vested share after the cliff!", async () => {
+    console.log("Waiting for the cliff...");
+    await delay((schedule.cliff_time + 2) * 1000 - Date.now());
+
+    const total = test_data.team_amount * LAMPORTS_PER_SOL;
+    const vested = (now: number) =>
+      Math.floor((total * (now - schedule.end_time)) / (schedule.vesting_end_time - schedule.end_time));
+    const before = await tokenBalance(new_owner_auctiontoken_ata);
+    const earliest = Math.floor(Date.now() / 1000);
+    const tx = await claimVestedTokens(new_owner);
+    const latest = Math.ceil(Date.now() / 1000);
+    console.log("claim_vested_tokens_tx", tx);
+
+    // The share vested since the sale ended, and not the whole allocation
+    const claimed = (await program.account.teamVesting.fetch(team_vesting)).claimed.toNumber();
+    assert.equal((await tokenBalance(new_owner_auctiontoken_ata)) - before, claimed);
+    assert.isAtLeast(claimed, vested(earliest - 2));
+    assert.isAtMost(claimed, vested(latest + 2));
+    assert.isBelow(claimed, total);
+    assert.equal(await tokenBalance(team_vault_ata), total - claimed);
+  });
+
+  it("Claim the rest once fully vested!", async () => {
+    console.log("Waiting for the end of vesting...");
+    await delay((schedule.vesting_end_time + 2) * 1000 - Date.now());
+
+    const total = test_data.team_amount * LAMPORTS_PER_SOL;
+    const already_claimed = (await program.account.teamVesting.fetch(team_vesting)).claimed.toNumber();
+    const before = await tokenBalance(new_owner_auctiontoken_ata);
+    console.log("claim_vested_tokens_tx", await claimVestedTokens(new_owner));
+
+    assert.equal((await tokenBalance(new_owner_auctiontoken_ata)) - before, total - already_claimed);
+    assert.equal((await program.account.teamVesting.fetch(team_vesting)).claimed.toNumber(), total);
+    assert.equal(await tokenBalance(team_vault_ata), 0);
+
+    // Nothing is left to claim
+    try {
+      await claimVestedTokens(new_owner);
+      assert.fail("vested tokens were claimed twice");
+    } catch (err) {
+      assert.include(err.toString(), "NothingToClaim");
+    }
+  });
+});